@@ -57,8 +57,8 @@ impl<'a> LazySubsequence<'a> {
     /// file underlying this corpus view, starting at the token number
     /// ``start``.  If ``start>=len(self)``, then this iterator will
     /// generate no tokens.
-    pub fn iterate_from(&self, start: usize) -> Skip<Graphemes> {
-        self.source.as_ref().graphemes(true).into_iter().skip(start)
+    pub fn iterate_from(&self, start: usize) -> Skip<Graphemes<'_>> {
+        self.source.as_ref().graphemes(true).skip(start)
     }
 
     /// Construct a new slice from a given underlying sequence.
@@ -191,3 +191,73 @@ impl<K, V> OrderedDict<K, V> {
         self.dict.get(key)
     }
 }
+
+/// A Trie implementation for sequences, e.g. the characters of strings
+/// or the tokens of multi-word expressions.
+#[derive(Debug)]
+pub struct Trie<K> {
+    children: HashMap<K, Trie<K>>,
+    leaf: bool,
+}
+impl<K> Default for Trie<K> {
+    fn default() -> Self {
+        Self {
+            children: HashMap::new(),
+            leaf: false,
+        }
+    }
+}
+impl<K> Trie<K>
+where
+    K: Eq + Hash,
+{
+    /// Builds an empty Trie
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts *keys* into the Trie
+    pub fn insert<I>(&mut self, keys: I)
+    where
+        I: IntoIterator<Item = K>,
+    {
+        let mut trie = self;
+        for key in keys {
+            trie = trie.children.entry(key).or_default();
+        }
+        trie.leaf = true;
+    }
+
+    /// Returns the sub-Trie of the sequences following *key*, if any
+    pub fn get<Q>(&self, key: &Q) -> Option<&Trie<K>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.children.get(key)
+    }
+
+    /// Returns if a sequence inserted in the Trie ends here
+    pub fn is_leaf(&self) -> bool {
+        self.leaf
+    }
+
+    /// Returns if the Trie doesn't hold any sequence
+    pub fn is_empty(&self) -> bool {
+        !self.leaf && self.children.is_empty()
+    }
+}
+impl<K, I> FromIterator<I> for Trie<K>
+where
+    K: Eq + Hash,
+    I: IntoIterator<Item = K>,
+{
+    /// Builds a Trie of the sequences
+    fn from_iter<T: IntoIterator<Item = I>>(iter: T) -> Self {
+        let mut trie = Self::new();
+        for keys in iter {
+            trie.insert(keys);
+        }
+        trie
+    }
+}
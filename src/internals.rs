@@ -86,7 +86,7 @@ use std::fmt;
 pub enum InternalError {
     /// Exception raised by read_* functions when they fail.
     ReadError,
-    ///
+    /// Exception raised when given an invalid value.
     ValueError,
 }
 
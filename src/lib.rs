@@ -15,6 +15,14 @@
 //! This is in no way supported by NLTK and is NOT responsible of any damages.
 //! Individual characters are going to be in Graphemes segments according
 //! to the [Unicode Standard Annex #29](http://www.unicode.org/reports/tr29/) rules.
+//! The spans returned by the tokenizers are byte offsets, an
+//! [OffsetMap](tokenize::util::OffsetMap) converts them into char, grapheme or UTF-16 offsets.
+//!
+//! # Features
+//!
+//! * ``parallel``: tokenizes batches of sentences and preprocesses items on a
+//!   [rayon](https://docs.rs/rayon) thread pool, see `TokenizerI::par_tokenize_sents`
+//!   and `util::parallelize_preprocess`.
 //!
 //! # Copyright
 //! NLTK is copyrighted and owned by the NLTK project.
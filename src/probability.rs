@@ -31,13 +31,22 @@ use std::ops::{Add, BitAnd, BitOr, Sub};
 
 #[inline(always)]
 fn _add_logs_max_diff() -> f64 {
-    f64::log(100.0, 2.0)
+    f64::log(1e-30, 2.0)
 }
 
 /// Given two numbers ``logx`` = *log(x)* and ``logy`` = *log(y)*, return
 /// *log(x+y)*.  Conceptually, this is the same as returning
 /// ``log(2**(logx)+2**(logy))``, but the actual implementation
 /// avoids overflow errors that could result from direct computation.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::probability::add_log;
+/// let result = add_log(f64::log2(0.25), f64::log2(0.5));
+/// # assert!((result - f64::log2(0.75)).abs() < 1e-12);
+/// ```
 pub fn add_log(logx: f64, logy: f64) -> f64 {
     if logx < logy + _add_logs_max_diff() {
         logy
@@ -45,7 +54,7 @@ pub fn add_log(logx: f64, logy: f64) -> f64 {
         logx
     } else {
         let base = f64::min(logx, logy);
-        base + f64::log(2_f64 * (logx - base) + 2_f64 * (logy - base), 2.0)
+        base + f64::log(2_f64.powf(logx - base) + 2_f64.powf(logy - base), 2.0)
     }
 }
 /// A frequency distribution for the outcomes of an experiment.  A
@@ -227,7 +236,7 @@ impl<T: Hash + Eq + Copy> BitAnd for FreqDist<T> {
     /// Intersection is the minimum of corresponding counts.
     fn bitand(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
-        lhs.counter = lhs.counter & rhs.counter;
+        lhs.counter &= rhs.counter;
         lhs
     }
 }
@@ -237,7 +246,7 @@ impl<T: Hash + Eq + Copy> BitOr for FreqDist<T> {
     /// Union is the maximum of value in either of the input counters.
     fn bitor(self, rhs: Self) -> Self::Output {
         let mut lhs = self;
-        lhs.counter = lhs.counter | rhs.counter;
+        lhs.counter |= rhs.counter;
         lhs
     }
 }
@@ -342,7 +351,7 @@ impl<T: Eq + Hash + Copy> UniformProbDist<T> {
         I: Iterator<Item = T>,
     {
         samples.for_each(|s| {
-            let _ = self.sampleset.push(s);
+            self.sampleset.push(s);
         });
     }
 }
@@ -354,7 +363,7 @@ impl<T: Eq + Hash + Copy> ProbDistI<T> for UniformProbDist<T> {
         0.0
     }
     fn max(&self) -> T {
-        *self.sampleset.get(0).unwrap()
+        *self.sampleset.first().unwrap()
     }
     fn samples(&self) -> Vec<T> {
         self.sampleset.clone()
@@ -364,6 +373,7 @@ impl<T: Eq + Hash + Copy> ProbDistI<T> for UniformProbDist<T> {
 /// Generates a random probability distribution whereby each sample
 /// will be between 0 and 1 with equal probability (uniform random distribution.
 /// Also called a continuous uniform distribution).
+#[allow(dead_code)]
 #[derive(Debug)]
 pub struct RandomProbDist<T> {
     sampleset: HashMap<T, f32>,
@@ -371,12 +381,12 @@ pub struct RandomProbDist<T> {
 impl<T: Eq + Hash + Copy> RandomProbDist<T> {
     /// Construct a new uniform probability distribution, that assigns
     /// equal probability to each sample in ``samples``.
-    pub fn new(mut self, samples: &[&T]) -> Self {
+    pub fn new(self, _samples: &[&T]) -> Self {
         todo!()
     }
 }
 impl<T: Eq + Hash + Copy> ProbDistI<T> for RandomProbDist<T> {
-    fn prob(&self, sample: T) -> f32 {
+    fn prob(&self, _sample: T) -> f32 {
         todo!()
     }
     fn max(&self) -> T {
@@ -1,33 +1,132 @@
 //! Tokenizer Interface
 
-use super::util::{string_span_tokenize, Token};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use super::util::Token;
 
 /// A processing interface for tokenizing a string.
-/// must define [Tokenizer::tokenize] or ``tokenize_sents()`` (or both).
-pub trait TokenizerI<'a> {
+/// must define [TokenizerI::tokenize] and [TokenizerI::span_tokenize].
+///
+/// The trait is object safe, so that a tokenizer picked at runtime can be kept
+/// as a `Box<dyn TokenizerI>`, see the [registry](super::registry) of the built-in tokenizers.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::regexp::WordPunctTokenizer;
+/// # use lib_nltk::tokenize::simple::SpaceTokenizer;
+/// let tokenizers: Vec<Box<dyn TokenizerI>> = vec![Box::new(SpaceTokenizer), Box::new(WordPunctTokenizer())];
+/// let text = String::from("Good muffins, please.");
+/// let result: Vec<_> = tokenizers.iter().map(|tokenizer| tokenizer.tokenize(&text)).collect();
+/// # assert_eq!(result, vec![vec!["Good", "muffins,", "please."], vec!["Good", "muffins", ",", "please", "."]]);
+/// ```
+pub trait TokenizerI {
     /// Return a tokenized copy of sent
-    fn tokenize(&self, _sent: &'a str) -> Vec<&'a str>;
+    fn tokenize<'a>(&self, _sent: &'a str) -> Vec<&'a str>;
     ///Identify the tokens using integer offsets [Token],
-    /// where ``_sent[start_i:end_i]`` is the corresponding token.
+    /// where ``&_sent[start_i..end_i]`` is the corresponding token.
+    ///
+    /// The offsets are always bytes, see [OffsetMap](super::util::OffsetMap) to convert them
+    /// into chars, graphemes or UTF-16 code units.
     fn span_tokenize(&self, _sent: &str) -> Vec<Token>;
     /// Apply [TokenizerI::tokenize] to each element of sentence
-    fn tokenize_sents(&self, sents: &[&'a str]) -> Vec<Vec<&'a str>> {
+    fn tokenize_sents<'a>(&self, sents: &[&'a str]) -> Vec<Vec<&'a str>> {
         sents.iter().map(|s| self.tokenize(s)).collect()
     }
     ///Apply [TokenizerI::span_tokenize] to each sentence.
-    fn span_tokenize_sents(&self, sents: &[&'a str]) -> Vec<Vec<Token>> {
+    ///
+    /// The spans are offsets into their sentence, see [Pipeline](super::pipeline::Pipeline)
+    /// to chain tokenizers with the spans offsets into the whole text.
+    fn span_tokenize_sents(&self, sents: &[&str]) -> Vec<Vec<Token>> {
         sents.iter().map(|s| self.span_tokenize(s)).collect()
     }
+    /// Apply [TokenizerI::tokenize] to the sentences in parallel,
+    /// the tokens are in the same order as the sentences.
+    ///
+    /// Requires the ``parallel`` feature.
+    #[cfg(feature = "parallel")]
+    fn par_tokenize_sents<'a>(&self, sents: &[&'a str]) -> Vec<Vec<&'a str>>
+    where
+        Self: Sync,
+    {
+        sents.par_iter().map(|s| self.tokenize(s)).collect()
+    }
+    /// Apply [TokenizerI::span_tokenize] to the sentences in parallel,
+    /// the spans are in the same order as the sentences.
+    ///
+    /// Requires the ``parallel`` feature.
+    #[cfg(feature = "parallel")]
+    fn par_span_tokenize_sents(&self, sents: &[&str]) -> Vec<Vec<Token>>
+    where
+        Self: Sync,
+    {
+        sents.par_iter().map(|s| self.span_tokenize(s)).collect()
+    }
+    /// Return an iterator over the tokens of sent, the same as [TokenizerI::tokenize].
+    ///
+    /// By default the tokens are all found before the first one is returned,
+    /// tokenizers that can find them one at a time return them lazily.
+    fn tokenize_iter<'a>(&self, sent: &'a str) -> impl Iterator<Item = &'a str>
+    where
+        Self: Sized,
+    {
+        self.tokenize(sent).into_iter()
+    }
+    /// Return an iterator over the spans of the tokens of sent, the same as [TokenizerI::span_tokenize].
+    ///
+    /// See [StreamTokenizer](super::stream::StreamTokenizer) to tokenize a text
+    /// that is read in chunks rather than held in memory.
+    fn span_tokenize_iter(&self, sent: &str) -> impl Iterator<Item = Token>
+    where
+        Self: Sized,
+    {
+        self.span_tokenize(sent).into_iter()
+    }
 }
 
-///A tokenizer that divides a string into substrings by splitting on the specified string
-pub trait StringTokenizer<'a>: TokenizerI<'a> {
-    #[allow(non_upper_case_globals)]
-    const _string: &'a str;
-    fn tokenize(&self, sent: &'a str) -> Vec<&'a str> {
-        sent.split(Self::_string).collect()
+/// A boxed tokenizer is a tokenizer too, so that it can be used wherever one is expected
+impl<T: TokenizerI + ?Sized> TokenizerI for Box<T> {
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        (**self).tokenize(sent)
     }
     fn span_tokenize(&self, sent: &str) -> Vec<Token> {
-        string_span_tokenize(sent, Self::_string).unwrap()
+        (**self).span_tokenize(sent)
+    }
+    fn tokenize_sents<'a>(&self, sents: &[&'a str]) -> Vec<Vec<&'a str>> {
+        (**self).tokenize_sents(sents)
+    }
+    fn span_tokenize_sents(&self, sents: &[&str]) -> Vec<Vec<Token>> {
+        (**self).span_tokenize_sents(sents)
     }
 }
+
+///A tokenizer that divides a string into substrings by splitting on the specified string
+pub trait StringTokenizer: TokenizerI {
+    /// The string the tokenizer splits on
+    fn separator(&self) -> &str;
+}
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_par_tokenize_sents() {
+    use super::regexp::WordPunctTokenizer;
+    use super::registry::build_tokenizer;
+
+    let sents: Vec<String> = (0..200)
+        .map(|i| format!("Sentence {i}: good muffins cost ${i}.88 in New York."))
+        .collect();
+    let sents: Vec<&str> = sents.iter().map(String::as_str).collect();
+
+    let tokenizer = WordPunctTokenizer();
+    assert_eq!(tokenizer.par_tokenize_sents(&sents), tokenizer.tokenize_sents(&sents));
+    assert_eq!(
+        tokenizer.par_span_tokenize_sents(&sents),
+        tokenizer.span_tokenize_sents(&sents)
+    );
+
+    let tokenizer = build_tokenizer("nltk_word", &Default::default()).unwrap();
+    assert_eq!(tokenizer.par_tokenize_sents(&sents), tokenizer.tokenize_sents(&sents));
+}
@@ -0,0 +1,393 @@
+//! Byte-Pair Encoding Tokenizer
+//!
+//! Byte-pair encoding (BPE) splits words into subwords, by merging the characters of
+//! the words pair by pair, most frequent pair first, as learnt from a corpus:
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::probability::FreqDist;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::bpe::BPETokenizer;
+//! let mut words: FreqDist<&str> = FreqDist::default();
+//! words.init("low low low low low lower lower newest newest newest widest".split(' '));
+//! let tokenizer = BPETokenizer::train(&words, 6);
+//! let result = tokenizer.tokenize("the lowest newer");
+//! # assert_eq!(result, vec!["t", "h", "e", "lo", "we", "st", "n", "ewe", "r"]);
+//! let ids: Vec<usize> = tokenizer.encode("the lowest newer").into_iter().map(|(id, _)| id).collect();
+//! let result = tokenizer.decode(&ids).unwrap();
+//! # assert_eq!(result, "<unk><unk><unk> lowest newer");
+//! ```
+//!
+//! The text is split into words on whitespace, and a word ending subword is told apart
+//! from the same subword within a word (``er</w>`` from ``er``), so that the words
+//! can be put back together by [BPETokenizer::decode].
+//!
+//! Reference:
+//! Rico Sennrich, Barry Haddow and Alexandra Birch. 2016. Neural Machine Translation
+//! of Rare Words with Subword Units. In Proceedings of ACL 2016.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use crate::probability::FreqDist;
+
+use super::api::TokenizerI;
+use super::util::{Token, TokenizeError};
+
+/// The marker appended to the subwords ending a word
+pub const END_OF_WORD: &str = "</w>";
+
+/// The subword of the characters that aren't in the vocabulary, its id is always 0,
+/// and 1 when it ends a word
+pub const UNKNOWN: &str = "<unk>";
+
+/// A subword tokenizer applying the merge rules of byte-pair encoding.
+///
+/// The vocabulary is the unknown subwords, then the characters seen in training, then
+/// the subwords of the merges, in the order they were learnt; the id of a subword is its
+/// index in the vocabulary.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BPETokenizer {
+    merges: Vec<(String, String)>,
+    ranks: HashMap<(String, String), usize>,
+    vocab: Vec<String>,
+    ids: HashMap<String, usize>,
+}
+
+impl BPETokenizer {
+    /// Learns at most *num_merges* merge rules from the frequency distribution of *words*.
+    ///
+    /// The pair of adjacent subwords occurring the most often is merged first; pairs
+    /// occurring as often are merged in lexicographic order, so the same words always give
+    /// the same merges. Fewer rules are learnt if every word is merged into a single subword.
+    pub fn train(words: &FreqDist<&str>, num_merges: usize) -> Self {
+        let mut words: Vec<(Vec<String>, usize)> = words
+            .list()
+            .into_iter()
+            .filter(|(word, _)| !word.is_empty())
+            .map(|(word, &count)| (symbols(word).into_iter().map(|(s, _)| s).collect(), count))
+            .collect();
+
+        let mut alphabet: Vec<String> = words
+            .iter()
+            .flat_map(|(word, _)| word.iter().cloned())
+            .collect();
+        alphabet.sort_unstable();
+        alphabet.dedup();
+
+        let mut merges = Vec::new();
+        while merges.len() < num_merges {
+            let mut pairs: HashMap<(&str, &str), usize> = HashMap::new();
+            for (word, count) in &words {
+                for pair in word.windows(2) {
+                    *pairs.entry((&pair[0], &pair[1])).or_insert(0) += count;
+                }
+            }
+            let best = pairs
+                .into_iter()
+                .max_by(|(a, count_a), (b, count_b)| count_a.cmp(count_b).then(b.cmp(a)));
+            let (first, second) = match best {
+                Some(((first, second), _)) => (first.to_string(), second.to_string()),
+                None => break,
+            };
+            for (word, _) in words.iter_mut() {
+                merge_pair(
+                    word,
+                    |a, b| *a == first && *b == second,
+                    |a, b| a.push_str(&b),
+                );
+            }
+            merges.push((first, second));
+        }
+        Self::new(alphabet, merges)
+    }
+
+    // Builds the tokenizer from its alphabet and merges, the vocabulary following from them
+    fn new(alphabet: Vec<String>, merges: Vec<(String, String)>) -> Self {
+        let mut vocab = vec![UNKNOWN.to_string(), format!("{}{}", UNKNOWN, END_OF_WORD)];
+        vocab.extend(alphabet);
+        vocab.extend(
+            merges
+                .iter()
+                .map(|(first, second)| format!("{}{}", first, second)),
+        );
+        let mut ids = HashMap::new();
+        for (id, subword) in vocab.iter().enumerate() {
+            ids.entry(subword.clone()).or_insert(id);
+        }
+        let ranks = merges
+            .iter()
+            .enumerate()
+            .map(|(rank, pair)| (pair.clone(), rank))
+            .collect();
+        Self {
+            merges,
+            ranks,
+            vocab,
+            ids,
+        }
+    }
+
+    /// Returns the merge rules, in the order they are applied
+    pub fn merges(&self) -> &[(String, String)] {
+        &self.merges
+    }
+
+    /// Returns the subwords of the vocabulary, by id
+    pub fn vocab(&self) -> &[String] {
+        &self.vocab
+    }
+
+    /// Returns the id of *subword*, if it is in the vocabulary
+    pub fn token_to_id(&self, subword: &str) -> Option<usize> {
+        self.ids.get(subword).copied()
+    }
+
+    /// Returns the subword of *id*, if it is in the vocabulary
+    pub fn id_to_token(&self, id: usize) -> Option<&str> {
+        self.vocab.get(id).map(String::as_str)
+    }
+
+    /// Returns the ids of the subwords of *text*, along with their spans.
+    ///
+    /// A subword that isn't in the vocabulary (one made of a character unseen in training)
+    /// has the id of [UNKNOWN].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::probability::FreqDist;
+    /// # use lib_nltk::tokenize::bpe::BPETokenizer;
+    /// let mut words: FreqDist<&str> = FreqDist::default();
+    /// words.init(["hug", "hug", "pug", "hugs"]);
+    /// let tokenizer = BPETokenizer::train(&words, 2);
+    /// let result: Vec<_> = tokenizer
+    ///     .encode("hugs bug")
+    ///     .into_iter()
+    ///     .map(|(id, span)| (tokenizer.id_to_token(id).unwrap(), span))
+    ///     .collect();
+    /// # assert_eq!(result, vec![("hu", (0, 2)), ("g", (2, 3)), ("s</w>", (3, 4)),
+    /// #     ("<unk>", (5, 6)), ("u", (6, 7)), ("g</w>", (7, 8))]);
+    /// ```
+    pub fn encode(&self, text: &str) -> Vec<(usize, Token)> {
+        self.subwords(text)
+            .into_iter()
+            .map(|(subword, span)| {
+                let unknown = usize::from(subword.ends_with(END_OF_WORD));
+                (self.token_to_id(&subword).unwrap_or(unknown), span)
+            })
+            .collect()
+    }
+
+    /// Returns the text of the subwords *ids*, the words separated by a space.
+    ///
+    /// Returns [TokenizeError::UnknownId] for an id that isn't in the vocabulary.
+    pub fn decode(&self, ids: &[usize]) -> Result<String, TokenizeError> {
+        let mut text = String::new();
+        for &id in ids {
+            let subword = self.id_to_token(id).ok_or(TokenizeError::UnknownId(id))?;
+            match subword.strip_suffix(END_OF_WORD) {
+                Some(subword) => {
+                    text.push_str(subword);
+                    text.push(' ');
+                }
+                None => text.push_str(subword),
+            }
+        }
+        Ok(text.trim_end().to_string())
+    }
+
+    /// Writes the vocabulary and merges out in the plain text format read by [BPETokenizer::load].
+    pub fn save<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "[alphabet]")?;
+        for symbol in &self.vocab[2..self.vocab.len() - self.merges.len()] {
+            writeln!(writer, "{}", symbol)?;
+        }
+        writeln!(writer, "[merges]")?;
+        for (first, second) in &self.merges {
+            writeln!(writer, "{}\t{}", first, second)?;
+        }
+        Ok(())
+    }
+
+    /// Reads in the vocabulary and merges written out by [BPETokenizer::save].
+    ///
+    /// Returns an [io::ErrorKind::InvalidData] error for a malformed line.
+    pub fn load<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid bpe vocabulary line: {:?}", line),
+            )
+        };
+
+        let mut alphabet = Vec::new();
+        let mut merges = Vec::new();
+        let mut section = String::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            // Only the exact headers start a section, a merge like "[\t]" holds a tab
+            if line == "[alphabet]" || line == "[merges]" {
+                section = line;
+                continue;
+            }
+            match section.as_str() {
+                "[alphabet]" => alphabet.push(line),
+                "[merges]" => {
+                    let (first, second) = line.split_once('\t').ok_or_else(|| invalid(&line))?;
+                    merges.push((first.to_string(), second.to_string()));
+                }
+                _ => return Err(invalid(&line)),
+            }
+        }
+        Ok(Self::new(alphabet, merges))
+    }
+
+    // Splits the text into words on whitespace, and the words into their subwords
+    fn subwords(&self, text: &str) -> Vec<(String, Token)> {
+        let mut result = Vec::new();
+        let mut start = None;
+        for (i, c) in text.char_indices().chain([(text.len(), ' ')]) {
+            match (start, c.is_whitespace()) {
+                (None, false) => start = Some(i),
+                (Some(s), true) => {
+                    result.extend(self.merge_word(&text[s..i], s));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+        result
+    }
+
+    // Applies the merges to a word at offset in the text, lowest ranked pair first
+    fn merge_word(&self, word: &str, offset: usize) -> Vec<(String, Token)> {
+        let mut subwords: Vec<(String, Token)> = symbols(word)
+            .into_iter()
+            .map(|(symbol, (start, end))| (symbol, (offset + start, offset + end)))
+            .collect();
+        loop {
+            let best = subwords
+                .windows(2)
+                .filter_map(|pair| {
+                    let key = (pair[0].0.clone(), pair[1].0.clone());
+                    self.ranks.get(&key).map(|&rank| (rank, key))
+                })
+                .min();
+            let (first, second) = match best {
+                Some((_, pair)) => pair,
+                None => break,
+            };
+            merge_pair(
+                &mut subwords,
+                |a, b| a.0 == first && b.0 == second,
+                |a, b| {
+                    a.0.push_str(&b.0);
+                    a.1 .1 = b.1 .1;
+                },
+            );
+        }
+        subwords
+    }
+}
+
+impl TokenizerI for BPETokenizer {
+    /// Returns the subwords of *sent*, without the [END_OF_WORD] markers
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
+    }
+
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        self.subwords(sent)
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect()
+    }
+}
+
+// The characters of a word and their spans, the last one marked as ending the word
+fn symbols(word: &str) -> Vec<(String, Token)> {
+    let mut symbols: Vec<(String, Token)> = word
+        .char_indices()
+        .map(|(i, c)| (c.to_string(), (i, i + c.len_utf8())))
+        .collect();
+    if let Some(last) = symbols.last_mut() {
+        last.0.push_str(END_OF_WORD);
+    }
+    symbols
+}
+
+// Merges the occurrences of the pairs of symbols, left to right
+fn merge_pair<T, P, F>(symbols: &mut Vec<T>, is_pair: P, merge: F)
+where
+    P: Fn(&T, &T) -> bool,
+    F: Fn(&mut T, T),
+{
+    let mut i = 0;
+    while i + 1 < symbols.len() {
+        if is_pair(&symbols[i], &symbols[i + 1]) {
+            let next = symbols.remove(i + 1);
+            merge(&mut symbols[i], next);
+        }
+        i += 1;
+    }
+}
+
+#[test]
+fn test_bpe_train() {
+    let mut words: FreqDist<&str> = FreqDist::default();
+    words.init("low low low low low lower lower newest newest newest widest".split(' '));
+    let tokenizer = BPETokenizer::train(&words, 4);
+    assert_eq!(
+        tokenizer.merges(),
+        [("l", "o"), ("lo", "w</w>"), ("w", "e"), ("s", "t</w>")]
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+    );
+    assert_eq!(
+        tokenizer.encode("lowest")[0],
+        (tokenizer.token_to_id("lo").unwrap(), (0, 2))
+    );
+
+    // every word merged whole
+    let tokenizer = BPETokenizer::train(&words, 100);
+    assert_eq!(
+        tokenizer.tokenize("widest newer"),
+        vec!["widest", "n", "ewe", "r"]
+    );
+
+    let mut saved = Vec::new();
+    tokenizer.save(&mut saved).unwrap();
+    let loaded = BPETokenizer::load(saved.as_slice()).unwrap();
+    assert_eq!(loaded, tokenizer);
+    assert!(BPETokenizer::load("[merges]\nab".as_bytes()).is_err());
+
+    let ids: Vec<usize> = tokenizer
+        .encode("  lower  widest")
+        .iter()
+        .map(|&(id, _)| id)
+        .collect();
+    assert_eq!(tokenizer.decode(&ids).unwrap(), "lower widest");
+    assert!(matches!(
+        tokenizer.decode(&[tokenizer.vocab().len()]),
+        Err(TokenizeError::UnknownId(_))
+    ));
+
+    // brackets merged into a symbol aren't mistaken for a section header
+    let mut bracketed: FreqDist<&str> = FreqDist::default();
+    bracketed.init("a[]b a[]b a[]b x[]".split(' '));
+    let brackets = BPETokenizer::train(&bracketed, 10);
+    assert!(brackets
+        .merges()
+        .contains(&("[".to_string(), "]".to_string())));
+    let mut saved = Vec::new();
+    brackets.save(&mut saved).unwrap();
+    let loaded = BPETokenizer::load(saved.as_slice()).unwrap();
+    assert_eq!(loaded, brackets);
+}
@@ -0,0 +1,483 @@
+//! Twitter-aware tokenizer, designed to be flexible and easy to adapt to new
+//! domains and tasks. The basic logic is this:
+//!
+//! 1. The tuple REGEXPS defines a list of regular expression
+//!    strings.
+//!
+//! 2. The REGEXPS strings are put, in order, into a compiled
+//!    regular expression object called WORD_RE, under the TweetTokenizer
+//!    class.
+//!
+//! 3. The tokenization is done by WORD_RE.findall(s), where s is the
+//!    user-supplied string, inside the tokenize() method of the class
+//!    TweetTokenizer.
+//!
+//! 4. When instantiating Tokenizer objects, there are several options:
+//!     * preserve_case. By default, it is set to True. If it is set to
+//!       False, then the tokenizer will downcase everything except for
+//!       emoticons.
+//!     * reduce_len. By default, it is set to False. It specifies whether
+//!       to replace repeated character sequences of length 3 or greater
+//!       with sequences of length 3.
+//!     * strip_handles. By default, it is set to False. It specifies
+//!       whether to remove Twitter handles of text used in the
+//!       `tokenize` method.
+//!     * match_phone_numbers. By default, it is set to True. It indicates
+//!       whether the `tokenize` method should look for phone numbers.
+//!
+//! Matches are extended to grapheme boundaries, so that emoji sequences joined
+//! with a zero width joiner or a skin tone modifier stay a single token.
+//!
+//! Copyright (C) 2001-2022 NLTK Project
+//! Author: Christopher Potts <cgpotts@stanford.edu>
+//!         Ewan Klein <ewan@inf.ed.ac.uk> (modifications)
+//!         Pierpaolo Pantone <> (modifications)
+//!         Tom Aarsen <> (modifications)
+//! URL: <https://www.nltk.org>
+//! For license information, see LICENSE.TXT
+
+use std::borrow::Cow;
+use std::iter::once;
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::api::TokenizerI;
+use super::util::Token;
+
+// The components of the tokenizer:
+
+// This particular element is used in a couple ways, so we define it
+// with a name:
+const EMOTICONS: &str = concat!(
+    r"(?:",
+    // eyes, optional nose, mouth
+    r"[<>]?[:;=8][\-o*']?[)\](\[dDpP/:}{@|\\]",
+    r"|",
+    // mouth, optional nose, eyes
+    r"[)\](\[dDpP/:}{@|\\][\-o*']?[:;=8][<>]?",
+    r"|",
+    // heart
+    r"</?3",
+    r")"
+);
+
+// URL pattern due to John Gruber, modified by Tom Winzig. See
+// https://gist.github.com/winzig/8894715
+const URLS: &str = concat!(
+    r"(?:",
+    // URL protocol and colon, followed by 1-3 slashes or a single letter, digit or '%'
+    // (Trying not to match e.g. "URI::Escape")
+    r"https?:(?:/{1,3}|[a-z0-9%])",
+    r"|",
+    // looks like domain name followed by a slash:
+    r"[a-z0-9.\-]+[.](?:[a-z]{2,13})/",
+    r")",
+    // One or more: run of non-space, non-()<>{}[] or balanced parens
+    r"(?:[^\s()<>{}\[\]]+|\([^\s()]*?\([^\s()]+\)[^\s()]*?\)|\([^\s]+?\))+",
+    // End with balanced parens or not a space or one of these punct chars
+    r#"(?:\([^\s()]*?\([^\s()]+\)[^\s()]*?\)|\([^\s]+?\)|[^\s`!()\[\]{};:'".,<>?«»“”‘’])"#,
+);
+
+// The naked domains, Python only matches those not preceded nor followed
+// by a @, avoiding "foo.na" in "foo.na@example.com".
+// The regex crate has no lookaround, so this is checked after matching.
+const NAKED_DOMAINS: &str = r"(?P<domain>[a-z0-9]+(?:[.\-][a-z0-9]+)*[.](?:[a-z]{2,13})\b/?)";
+
+// Regex for recognizing phone numbers:
+const PHONE_REGEX: &str = concat!(
+    r"(?:",
+    // (international)
+    r"(?:\+?[01][ *\-.)]*)?",
+    // (area code)
+    r"(?:[(]?\d{3}[ *\-.)]*)?",
+    // exchange
+    r"\d{3}[ *\-.)]*",
+    // base
+    r"\d{4}",
+    r")"
+);
+
+// The remaining regexes, in the order they are tried
+const REGEXPS: [&str; 11] = [
+    // ASCII Emoticons
+    EMOTICONS,
+    // HTML tags:
+    r"<[^>\s]+>",
+    // ASCII Arrows
+    r"[\-]+>|<[\-]+",
+    // Twitter username:
+    r"(?:@[\w_]+)",
+    // Twitter hashtags:
+    r"(?:#+[\w_]+[\w'_\-]*[\w_]+)",
+    // email addresses
+    r"[\w.+-]+@[\w-]+\.(?:[\w-]\.?)+[\w-]",
+    // Remaining word types:
+    // Words with apostrophes or dashes.
+    r"(?:[^\W\d_](?:[^\W\d_]|['\-_])+[^\W\d_])",
+    // Numbers, including fractions, decimals.
+    r"(?:[+\-]?\d+[,/.:-]\d+[+\-]?)",
+    // Words without apostrophes or dashes.
+    r"(?:[\w_]+)",
+    // Ellipsis dots.
+    r"(?:\.(?:\s*\.){1,})",
+    // Everything else that isn't whitespace.
+    r"(?:\S)",
+];
+
+// Builds the core tokenizing regex, optionally looking for phone numbers and naked domains
+fn word_re(phone_numbers: bool, naked_domains: bool) -> Regex {
+    let urls = if naked_domains {
+        format!("{}|{}", URLS, NAKED_DOMAINS)
+    } else {
+        URLS.to_string()
+    };
+    let mut patterns = vec![urls.as_str()];
+    if phone_numbers {
+        patterns.push(PHONE_REGEX);
+    }
+    patterns.extend(REGEXPS);
+    Regex::new(&format!("(?i){}", patterns.join("|"))).unwrap()
+}
+
+lazy_static! {
+    static ref WORD_RE: Regex = word_re(false, true);
+    static ref WORD_RE_NO_DOMAINS: Regex = word_re(false, false);
+    static ref PHONE_WORD_RE: Regex = word_re(true, true);
+    static ref PHONE_WORD_RE_NO_DOMAINS: Regex = word_re(true, false);
+    static ref EMOTICON_RE: Regex = Regex::new(&format!("(?i){}", EMOTICONS)).unwrap();
+}
+
+// Returns the spans of the Twitter handles, which Python matches with
+// `(?<![A-Za-z0-9_!@#\$%&*])@(([A-Za-z0-9_]){15}(?!@)|([A-Za-z0-9_]){1,14}(?![A-Za-z0-9_]*@))`
+fn handle_spans(text: &str) -> Vec<Token> {
+    let bytes = text.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut result = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let preceded = i > 0 && (is_word(bytes[i - 1]) || b"!@#$%&*".contains(&bytes[i - 1]));
+        if bytes[i] == b'@' && !preceded {
+            let n = bytes[i + 1..].iter().take_while(|&&b| is_word(b)).count();
+            let len = if n >= 15 && bytes.get(i + 16) != Some(&b'@') {
+                15
+            } else if bytes.get(i + 1 + n) != Some(&b'@') {
+                n.min(14)
+            } else {
+                0
+            };
+            if len > 0 {
+                result.push((i, i + 1 + len));
+                i += 1 + len;
+                continue;
+            }
+        }
+        i += 1;
+    }
+    result
+}
+
+// The text the regexes run on, the handles and repeated characters removed,
+// along with the original span of each of its graphemes
+struct SafeText {
+    text: String,
+    // (offset in text, start in original, end in original)
+    graphemes: Vec<(usize, usize, usize)>,
+}
+impl SafeText {
+    // Returns the index of the grapheme containing the offset
+    fn grapheme_at(&self, offset: usize) -> usize {
+        self.graphemes.partition_point(|&(i, _, _)| i <= offset) - 1
+    }
+
+    // Returns the offset the grapheme ends at in the text
+    fn grapheme_end(&self, index: usize) -> usize {
+        self.graphemes
+            .get(index + 1)
+            .map_or(self.text.len(), |&(i, _, _)| i)
+    }
+}
+
+/// Tokenizer for tweets.
+///
+/// Spans are the byte offsets of the tokens in the input text. When characters
+/// are repeated, the span of the token ending the run covers the whole run.
+///
+/// Note: HTML entities aren't replaced, as the tokens are slices of the input text.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::casual::TweetTokenizer;
+/// let tknzr = TweetTokenizer::new();
+/// let s0 = "This is a cooool #dummysmiley: :-) :-P <3 and some arrows < > -> <--";
+/// let result = tknzr.tokenize(s0);
+/// # assert_eq!(result, vec!["This", "is", "a", "cooool", "#dummysmiley", ":", ":-)", ":-P", "<3", "and",
+/// #     "some", "arrows", "<", ">", "->", "<--"]);
+/// ```
+///
+/// Examples using *strip_handles* and *reduce_len* parameters:
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::casual::TweetTokenizer;
+/// let tknzr = TweetTokenizer::new().set_strip_handles(true).set_reduce_len(true);
+/// let s1 = "@remy: This is waaaaayyyy too much for you!!!!!!";
+/// let result = tknzr.tokenize_normalized(s1);
+/// # assert_eq!(result, vec![":", "This", "is", "waaayyy", "too", "much", "for", "you", "!", "!", "!"]);
+/// ```
+#[derive(Debug)]
+pub struct TweetTokenizer {
+    preserve_case: bool,
+    reduce_len: bool,
+    strip_handles: bool,
+    match_phone_numbers: bool,
+}
+impl TokenizerI for TweetTokenizer {
+    /// Tokenize the input text, returning the tokens as slices of *sent*.
+    ///
+    /// Case folding and length reduction can't be applied to slices of the text,
+    /// use [TweetTokenizer::tokenize_normalized] for those.
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
+    }
+
+    /// Identify the tokens using integer offsets [Token]
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        let safe_text = self.safe_text(sent);
+        self.find_tokens(&safe_text)
+            .into_iter()
+            .map(|(start, end)| (safe_text.graphemes[start].1, safe_text.graphemes[end].2))
+            .collect()
+    }
+}
+
+impl Default for TweetTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TweetTokenizer {
+    /// Initializes an instance of the [TweetTokenizer] struct,
+    /// preserving case, not reducing lengths nor stripping handles and matching phone numbers
+    pub const fn new() -> Self {
+        Self {
+            preserve_case: true,
+            reduce_len: false,
+            strip_handles: false,
+            match_phone_numbers: true,
+        }
+    }
+
+    /// Sets if the case is preserved, otherwise everything but the emoticons
+    /// is lower cased by [TweetTokenizer::tokenize_normalized].
+    ///
+    /// Defaults to 'TRUE'
+    pub fn set_preserve_case(mut self, yes: bool) -> Self {
+        self.preserve_case = yes;
+        self
+    }
+
+    /// Sets if character sequences of length 3 or greater are replaced
+    /// with sequences of length 3 ("soooooo" becomes "sooo").
+    ///
+    /// Defaults to 'FALSE'
+    pub fn set_reduce_len(mut self, yes: bool) -> Self {
+        self.reduce_len = yes;
+        self
+    }
+
+    /// Sets if Twitter handles are removed from the text.
+    ///
+    /// Defaults to 'FALSE'
+    pub fn set_strip_handles(mut self, yes: bool) -> Self {
+        self.strip_handles = yes;
+        self
+    }
+
+    /// Sets if phone numbers are kept as single tokens.
+    ///
+    /// Defaults to 'TRUE'
+    pub fn set_match_phone_numbers(mut self, yes: bool) -> Self {
+        self.match_phone_numbers = yes;
+        self
+    }
+
+    /// Tokenize the input text, reducing the lengthening and lower casing the tokens
+    /// (except for the emoticons) as configured. Unchanged tokens are borrowed from *text*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::casual::TweetTokenizer;
+    /// let tknzr = TweetTokenizer::new().set_preserve_case(false);
+    /// let result = tknzr.tokenize_normalized("GREAT news :-D Call 555-123-4567 NOW");
+    /// # assert_eq!(result, vec!["great", "news", ":-D", "call", "555-123-4567", "now"]);
+    /// ```
+    pub fn tokenize_normalized<'a>(&self, text: &'a str) -> Vec<Cow<'a, str>> {
+        let safe_text = self.safe_text(text);
+        self.find_tokens(&safe_text)
+            .into_iter()
+            .map(|(start, end)| {
+                let original = &text[safe_text.graphemes[start].1..safe_text.graphemes[end].2];
+                let safe =
+                    &safe_text.text[safe_text.graphemes[start].0..safe_text.grapheme_end(end)];
+                let token = if safe == original {
+                    Cow::Borrowed(original)
+                } else {
+                    Cow::Owned(safe.to_string())
+                };
+                if self.preserve_case || EMOTICON_RE.is_match(&token) {
+                    token
+                } else {
+                    Cow::Owned(token.to_lowercase())
+                }
+            })
+            .collect()
+    }
+
+    // Removes the handles and repeated characters from the text,
+    // a removed handle is replaced by a space.
+    fn safe_text(&self, text: &str) -> SafeText {
+        let mut safe_text = SafeText {
+            text: String::with_capacity(text.len()),
+            graphemes: Vec::new(),
+        };
+        let handles = if self.strip_handles {
+            handle_spans(text)
+        } else {
+            vec![]
+        };
+        let mut pos = 0;
+        for (start, end) in handles.into_iter().chain(once((text.len(), text.len()))) {
+            self.push_runs(&text[pos..start], pos, &mut safe_text);
+            if start < end {
+                safe_text.graphemes.push((safe_text.text.len(), start, end));
+                safe_text.text.push(' ');
+            }
+            pos = end;
+        }
+        safe_text
+    }
+
+    // Adds the graphemes of the segment, shortening the runs of the same grapheme:
+    // - to 3 if the length is reduced and the run is 3 or longer.
+    // - to 3 if the run is 4 or longer and isn't alphanumeric, as those can hang the regexes.
+    fn push_runs(&self, segment: &str, offset: usize, safe_text: &mut SafeText) {
+        let graphemes: Vec<(usize, &str)> = segment.grapheme_indices(true).collect();
+        let mut i = 0;
+        while i < graphemes.len() {
+            let grapheme = graphemes[i].1;
+            let n = graphemes[i..]
+                .iter()
+                .take_while(|(_, c)| *c == grapheme)
+                .count();
+            let alphanumeric =
+                grapheme.len() == 1 && grapheme.as_bytes()[0].is_ascii_alphanumeric();
+            let keep = if (self.reduce_len && n >= 3) || (n >= 4 && !alphanumeric) {
+                3
+            } else {
+                n
+            };
+            let run_end = offset + graphemes.get(i + n).map_or(segment.len(), |(j, _)| *j);
+            for (k, &(j, c)) in graphemes[i..i + keep].iter().enumerate() {
+                let end = if k + 1 == keep {
+                    run_end
+                } else {
+                    offset + j + c.len()
+                };
+                safe_text
+                    .graphemes
+                    .push((safe_text.text.len(), offset + j, end));
+                safe_text.text.push_str(c);
+            }
+            i += n;
+        }
+    }
+
+    // Returns the indices of the first and last graphemes of each token in the safe text
+    fn find_tokens(&self, safe_text: &SafeText) -> Vec<(usize, usize)> {
+        let (word_re, no_domains_re) = if self.match_phone_numbers {
+            (&*PHONE_WORD_RE, &*PHONE_WORD_RE_NO_DOMAINS)
+        } else {
+            (&*WORD_RE, &*WORD_RE_NO_DOMAINS)
+        };
+        let text = safe_text.text.as_str();
+        let mut result = Vec::new();
+        let mut pos = 0;
+        while let Some(caps) = word_re.captures_at(text, pos) {
+            let m = caps.get(0).unwrap();
+            let (mut start, mut end) = (m.start(), m.end());
+            if caps.name("domain").is_some()
+                && (text[..start].ends_with('@') || text[end..].starts_with('@'))
+            {
+                // The naked domain can't match, the remaining regexes always match a non-space
+                let m = no_domains_re.find_at(text, start).unwrap();
+                (start, end) = (m.start(), m.end());
+            }
+            // Extend the match to whole graphemes
+            let first = usize::max(safe_text.grapheme_at(start), safe_text.grapheme_at(pos));
+            let last = safe_text.grapheme_at(end - 1);
+            result.push((first, last));
+            pos = safe_text.grapheme_end(last);
+        }
+        result
+    }
+}
+
+/// Convenience function for wrapping the tokenizer.
+pub fn casual_tokenize(
+    text: &str,
+    preserve_case: bool,
+    reduce_len: bool,
+    strip_handles: bool,
+) -> Vec<Cow<'_, str>> {
+    TweetTokenizer::new()
+        .set_preserve_case(preserve_case)
+        .set_reduce_len(reduce_len)
+        .set_strip_handles(strip_handles)
+        .tokenize_normalized(text)
+}
+
+#[test]
+fn test_tweet_span_tokenize() {
+    let tknzr = TweetTokenizer::new();
+    let text = "Mail me@example.com or visit https://t.co/x1 👩‍👩‍👧 👍🏽!! foo.na@example.com";
+    assert_eq!(
+        tknzr.tokenize(text),
+        vec![
+            "Mail",
+            "me@example.com",
+            "or",
+            "visit",
+            "https://t.co/x1",
+            "👩‍👩‍👧",
+            "👍🏽",
+            "!",
+            "!",
+            "foo.na@example.com"
+        ]
+    );
+    let text = "@handle sooooo goooood!!!!!";
+    let tknzr = tknzr.set_strip_handles(true).set_reduce_len(true);
+    assert_eq!(
+        tknzr.span_tokenize(text),
+        vec![(8, 14), (15, 22), (22, 23), (23, 24), (24, 27)]
+    );
+    assert_eq!(
+        tknzr.tokenize_normalized(text),
+        vec!["sooo", "goood", "!", "!", "!"]
+    );
+    assert_eq!(
+        casual_tokenize("Visit example.com NOW", false, false, false),
+        vec!["visit", "example.com", "now"]
+    );
+}
@@ -0,0 +1,238 @@
+//! CJK Word Segmentation
+//!
+//! Chinese and Japanese are written without spaces between the words, so a
+//! ``CJKTokenizer`` finds the runs of CJK characters (see [is_cjk]) and segments them
+//! by maximum matching against a lexicon, leaving the rest of the text to a delegate
+//! tokenizer:
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::cjk::CJKTokenizer;
+//! let tokenizer = CJKTokenizer::new(&["我们", "喜欢", "纽约"]);
+//! let result = tokenizer.tokenize("我们喜欢New York和纽约的muffins。");
+//! # assert_eq!(result, vec!["我们", "喜欢", "New", "York", "和", "纽约", "的", "muffins", "。"]);
+//! ```
+//!
+//! A CJK character that doesn't start (or end, matching backward) a word of the
+//! lexicon is a token of its own.
+
+use crate::collections::Trie;
+
+use super::api::TokenizerI;
+use super::regexp::{RegexpTokenizer, WhitespaceTokenizer};
+use super::util::{is_cjk, Token};
+
+/// The directions the runs of CJK characters are matched against the lexicon in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchDirection {
+    /// Forward maximum matching, the longest word starting at each character
+    Forward,
+    /// Backward maximum matching, the longest word ending at each character, from the end of the run
+    Backward,
+    /// Both directions, keeping the segmentation with the fewest words, then the fewest
+    /// single characters, then the backward one
+    Bidirectional,
+}
+
+/// A tokenizer segmenting the runs of CJK characters by maximum matching against a
+/// lexicon, and tokenizing the rest of the text with a delegate tokenizer
+/// (a [WhitespaceTokenizer] by default).
+///
+/// The spans are the byte offsets of the tokens in the original text.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::cjk::{CJKTokenizer, MatchDirection};
+/// # use lib_nltk::tokenize::regexp::WordPunctTokenizer;
+/// let lexicon = ["研究", "研究生", "生命", "起源"];
+/// let tokenizer = CJKTokenizer::with_delegate(&lexicon, WordPunctTokenizer());
+/// let result = tokenizer.tokenize("研究生命起源(DNA)");
+/// # assert_eq!(result, vec!["研究", "生命", "起源", "(", "DNA", ")"]);
+/// let tokenizer = tokenizer.set_direction(MatchDirection::Forward);
+/// let result = tokenizer.tokenize("研究生命起源(DNA)");
+/// # assert_eq!(result, vec!["研究生", "命", "起源", "(", "DNA", ")"]);
+/// ```
+#[derive(Debug)]
+pub struct CJKTokenizer<T = RegexpTokenizer> {
+    // The words of the lexicon, and the same words reversed for backward matching
+    words: Trie<char>,
+    reversed: Trie<char>,
+    direction: MatchDirection,
+    delegate: T,
+}
+
+impl CJKTokenizer {
+    /// Initializes a [CJKTokenizer] segmenting CJK text with the words of *lexicon*,
+    /// and the rest of the text with a [WhitespaceTokenizer].
+    pub fn new<S: AsRef<str>>(lexicon: &[S]) -> Self {
+        Self::with_delegate(lexicon, WhitespaceTokenizer())
+    }
+}
+
+impl<T: TokenizerI> CJKTokenizer<T> {
+    /// Initializes a [CJKTokenizer] segmenting CJK text with the words of *lexicon*,
+    /// and the rest of the text with *delegate*.
+    pub fn with_delegate<S: AsRef<str>>(lexicon: &[S], delegate: T) -> Self {
+        let mut tokenizer = Self {
+            words: Trie::new(),
+            reversed: Trie::new(),
+            direction: MatchDirection::Bidirectional,
+            delegate,
+        };
+        for word in lexicon {
+            tokenizer.add_word(word.as_ref());
+        }
+        tokenizer
+    }
+
+    /// Add a word to the lexicon, an empty word is ignored
+    pub fn add_word(&mut self, word: &str) {
+        if !word.is_empty() {
+            self.words.insert(word.chars());
+            self.reversed.insert(word.chars().rev());
+        }
+    }
+
+    /// Sets the direction the CJK runs are matched in.
+    ///
+    /// Defaults to [MatchDirection::Bidirectional]
+    pub fn set_direction(mut self, direction: MatchDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    // Segments a run of CJK characters, returning the spans of the words
+    // as indices into the chars of the run
+    fn segment(&self, run: &[char]) -> Vec<(usize, usize)> {
+        match self.direction {
+            MatchDirection::Forward => self.forward(run),
+            MatchDirection::Backward => self.backward(run),
+            MatchDirection::Bidirectional => {
+                let forward = self.forward(run);
+                let backward = self.backward(run);
+                let singles = |words: &[(usize, usize)]| {
+                    words.iter().filter(|(start, end)| end - start == 1).count()
+                };
+                if (forward.len(), singles(&forward)) < (backward.len(), singles(&backward)) {
+                    forward
+                } else {
+                    backward
+                }
+            }
+        }
+    }
+
+    fn forward(&self, run: &[char]) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+        let mut start = 0;
+        while start < run.len() {
+            let end = longest_match(&self.words, run[start..].iter())
+                .map_or(start + 1, |len| start + len);
+            result.push((start, end));
+            start = end;
+        }
+        result
+    }
+
+    fn backward(&self, run: &[char]) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+        let mut end = run.len();
+        while end > 0 {
+            let start = longest_match(&self.reversed, run[..end].iter().rev())
+                .map_or(end - 1, |len| end - len);
+            result.push((start, end));
+            end = start;
+        }
+        result.reverse();
+        result
+    }
+}
+
+// Returns the length of the longest word of the trie the chars start with, if any
+fn longest_match<'c>(trie: &Trie<char>, chars: impl Iterator<Item = &'c char>) -> Option<usize> {
+    let mut trie = trie;
+    let mut last_match = None;
+    for (i, c) in chars.enumerate() {
+        match trie.get(c) {
+            Some(child) => trie = child,
+            None => break,
+        }
+        if trie.is_leaf() {
+            last_match = Some(i + 1);
+        }
+    }
+    last_match
+}
+
+// Returns if the char is segmented with the lexicon, the ideographic space being a space
+fn is_cjk_word_char(c: char) -> bool {
+    is_cjk(c) && !c.is_whitespace()
+}
+
+impl<T: TokenizerI> TokenizerI for CJKTokenizer<T> {
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
+    }
+
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        let mut result = Vec::new();
+        let mut chars = sent.char_indices().peekable();
+        while let Some(&(start, c)) = chars.peek() {
+            let cjk = is_cjk_word_char(c);
+            let mut run = Vec::new();
+            while let Some(&(_, c)) = chars.peek() {
+                if is_cjk_word_char(c) != cjk {
+                    break;
+                }
+                run.push(chars.next().unwrap());
+            }
+            let end = chars.peek().map_or(sent.len(), |&(i, _)| i);
+
+            if cjk {
+                let text: Vec<char> = run.iter().map(|&(_, c)| c).collect();
+                let offset = |i: usize| run.get(i).map_or(end, |&(offset, _)| offset);
+                result.extend(
+                    self.segment(&text)
+                        .into_iter()
+                        .map(|(s, e)| (offset(s), offset(e))),
+                );
+            } else {
+                result.extend(
+                    self.delegate
+                        .span_tokenize(&sent[start..end])
+                        .into_iter()
+                        .map(|(s, e)| (start + s, start + e)),
+                );
+            }
+        }
+        result
+    }
+}
+
+#[test]
+fn test_cjk_tokenize() {
+    let tokenizer = CJKTokenizer::new(&["東京", "東京都", "京都", "に", "住む"]);
+    let text = "東京都に住む　Tokyo";
+    let spans = tokenizer.span_tokenize(text);
+    assert_eq!(spans, vec![(0, 9), (9, 12), (12, 18), (21, 26)]);
+    assert_eq!(
+        tokenizer.tokenize(text),
+        vec!["東京都", "に", "住む", "Tokyo"]
+    );
+
+    // unknown characters fall back to single characters, in either direction
+    for direction in [MatchDirection::Forward, MatchDirection::Backward] {
+        let tokenizer = CJKTokenizer::new(&["京都"]).set_direction(direction);
+        assert_eq!(tokenizer.tokenize("京都大学"), vec!["京都", "大", "学"]);
+    }
+    let tokenizer = CJKTokenizer::new(&[""]);
+    assert_eq!(tokenizer.tokenize("大学 ok"), vec!["大", "学", "ok"]);
+    assert!(tokenizer.tokenize("").is_empty());
+}
@@ -6,29 +6,85 @@
 //! URL: <https://www.nltk.org>
 //! For license information, see LICENSE.TXT
 
-use super::{api::TokenizerI, util::Token};
+use super::api::TokenizerI;
+use super::util::{
+    align_found_tokens, align_tokens, Normalization, Token, TokenizeError, CONVERTED_QUOTES,
+    CONVERT_PARENTHESES,
+};
 
 use lazy_static::lazy_static;
-use regex::RegexSet;
+use regex::{Captures, Regex};
 
 // MacIntyreContractions
 //List of contractions adapted from Robert MacIntyre's tokenizer.
+//The empty group `(?:)` marks the place the contraction is split at.
+pub(crate) const MACINTYRE_CONTRACTIONS2: [&str; 8] = [
+    r"(?i)\b(can)(?:)(not)\b",
+    r"(?i)\b(d)(?:)('ye)\b",
+    r"(?i)\b(gim)(?:)(me)\b",
+    r"(?i)\b(gon)(?:)(na)\b",
+    r"(?i)\b(got)(?:)(ta)\b",
+    r"(?i)\b(lem)(?:)(me)\b",
+    r"(?i)\b(more)(?:)('n)\b",
+    // Python uses the lookahead `(?=\s)`, which isn't supported by the regex crate.
+    // The whitespace is consumed instead and put back by the substitution.
+    r"(?i)\b(wan)(?:)(na)\s",
+];
+pub(crate) const MACINTYRE_CONTRACTIONS3: [&str; 2] =
+    [r"(?i) ('t)(?:)(is)\b", r"(?i) ('t)(?:)(was)\b"];
+// We are not using CONTRACTIONS4 since they are also commented out in the SED scripts
+#[allow(dead_code)]
+const MACINTYRE_CONTRACTIONS4: [&str; 2] = [r"(?i)\b(whad)(dd)(ya)\b", r"(?i)\b(wha)(t)(cha)\b"];
+
+fn compile_all(patterns: &[&str]) -> Vec<Regex> {
+    patterns.iter().map(|p| Regex::new(p).unwrap()).collect()
+}
+
 lazy_static! {
-    static ref CONTRACTIONS2: RegexSet = RegexSet::new(&[
-        r"(?i)\b(can)(not)\b",
-        r"(?i)\b(d)('ye)\b",
-        r"(?i)\b(gim)(me)\b",
-        r"(?i)\b(gon)(na)\b",
-        r"(?i)\b(got)(ta)\b",
-        r"(?i)\b(lem)(me)\b",
-        r"(?i)\b(more)('n)\b",
-        r"(?i)\b(wan)(na)\b"
-    ])
-    .unwrap();
-    static ref CONTRACTIONS3: RegexSet =
-        RegexSet::new(&[r"(?i) ('t)(is)\b", r"(?i) ('t)(was)\b"]).unwrap();
-    static ref CONTRACTIONS4: RegexSet =
-        RegexSet::new(&[r"(?i)\b(whad)(dd)(ya)\b", r"(?i)\b(wha)(t)(cha)\b"]).unwrap();
+    static ref TREEBANK: Normalization = Normalization::treebank();
+
+    static ref CONTRACTIONS2: Vec<Regex> = compile_all(&MACINTYRE_CONTRACTIONS2);
+    static ref CONTRACTIONS3: Vec<Regex> = compile_all(&MACINTYRE_CONTRACTIONS3);
+
+    // Starting quotes.
+    static ref STARTING_QUOTES: Vec<(Regex, &'static str)> = vec![
+        (Regex::new(r"([«“‘„]|[`]+)").unwrap(), " $1 "),
+        (Regex::new(r#"^""#).unwrap(), "``"),
+        (Regex::new(r"(``)").unwrap(), " $1 "),
+        (Regex::new(r#"([ \(\[{<])("|'{2})"#).unwrap(), "$1 `` "),
+    ];
+    // Splits a single quote from the next word unless it is a clitic
+    // Python: (?i)(\')(?!re|ve|ll|m|t|s|d|n)(\w)\b
+    static ref STARTING_CLITIC: Regex = Regex::new(r"(')(\w)\b").unwrap();
+
+    // Punctuation.
+    static ref PUNCTUATION: Vec<(Regex, &'static str)> = vec![
+        (Regex::new(r#"([^\.])(\.)([\]\)}>"'»”’ ]*)\s*$"#).unwrap(), "$1 $2 $3 "),
+        (Regex::new(r"([:,])([^\d])").unwrap(), " $1 $2"),
+        (Regex::new(r"([:,])$").unwrap(), " $1 "),
+        (Regex::new(r"\.{2,}").unwrap(), " $0 "),
+        (Regex::new(r"[;@#$%&]").unwrap(), " $0 "),
+        // Handles the final period.
+        (Regex::new(r#"([^\.])(\.)([\]\)}>"']*)\s*$"#).unwrap(), "$1 $2$3 "),
+        (Regex::new(r"[?!]").unwrap(), " $0 "),
+        (Regex::new(r"([^'])' ").unwrap(), "$1 ' "),
+        (Regex::new(r"[*]").unwrap(), " $0 "),
+    ];
+
+    // Pads parentheses
+    static ref PARENS_BRACKETS: Regex = Regex::new(r"[\]\[\(\)\{\}<>]").unwrap();
+
+    // Handles double dash.
+    static ref DOUBLE_DASHES: Regex = Regex::new(r"--").unwrap();
+
+    // Ending quotes.
+    static ref ENDING_QUOTES: Vec<(Regex, &'static str)> = vec![
+        (Regex::new(r"([»”’])").unwrap(), " $1 "),
+        (Regex::new(r"''").unwrap(), " '' "),
+        (Regex::new(r#"""#).unwrap(), " '' "),
+        (Regex::new(r"([^' ])('[sS]|'[mM]|'[dD]|') ").unwrap(), "$1 $2 "),
+        (Regex::new(r"([^' ])('ll|'LL|'re|'RE|'ve|'VE|n't|N'T) ").unwrap(), "$1 $2 "),
+    ];
 }
 
 /// The NLTK tokenizer that has improved upon the TreebankWordTokenizer.
@@ -38,19 +94,242 @@ lazy_static! {
 ///
 /// The tokenizer is "destructive" such that the regexes applied will munge the
 /// input string to a state beyond re-construction. It is possible to apply
-/// `TreebankWordDetokenizer.detokenize` to the tokenized outputs of
-/// `NLTKDestructiveWordTokenizer.tokenize` but there's no guarantees to
+/// [TreebankWordDetokenizer::detokenize](super::treebank::TreebankWordDetokenizer::detokenize)
+/// to the tokenized outputs of [NLTKWordTokenizer] but there's no guarantees to
 /// revert to the original string.
-pub struct NLTKWordTokenizer;
-impl<'a> TokenizerI<'a> for NLTKWordTokenizer {
+///
+/// Every token returned is either a slice of the input, or one of the converted
+/// symbols (``` `` ```, `''`, and the `-LRB-` style brackets).
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::destructive::NLTKWordTokenizer;
+/// let s = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\nThanks.";
+/// let expected = vec!["Good", "muffins", "cost", "$", "3.88", "in", "New", "York.", "Please", "buy", "me", "two", "of", "them.", "Thanks", "."];
+/// let result = NLTKWordTokenizer::new().tokenize(s);
+/// # assert_eq!(result, expected);
+/// ```
+#[derive(Debug, Default)]
+pub struct NLTKWordTokenizer {
+    convert_parentheses: bool,
+}
+impl TokenizerI for NLTKWordTokenizer {
     /// Return a tokenized copy of `text`.
-    fn tokenize(&self, _sent: &'a str) -> Vec<&'a str> {
-        todo!()
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.align(sent).into_iter().map(|(t, _)| t).collect()
     }
 
     /// Returns the spans of the tokens in ``text``.
-    /// Uses the post-hoc nltk.tokens.align_tokens to return the offset spans.
-    fn span_tokenize(&self, _sent: &str) -> Vec<Token> {
-        todo!()
+    /// The spans are the byte offsets of the token within the input string,
+    /// converted quotes and brackets are given the span of the original characters.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::api::TokenizerI;
+    /// # use lib_nltk::tokenize::destructive::NLTKWordTokenizer;
+    /// let s = "Good muffins cost $3.88\nin New (York).  Please (buy) me\ntwo of them.\n(Thanks).";
+    /// let expected = vec![(0, 4), (5, 12), (13, 17), (18, 19), (19, 23), (24, 26), (27, 30), (31, 32), (32, 36), (36, 37), (37, 38), (40, 46), (47, 48), (48, 51), (51, 52), (53, 55), (56, 59), (60, 62), (63, 68), (69, 70), (70, 76), (76, 77), (77, 78)];
+    /// let result = NLTKWordTokenizer::new().span_tokenize(s);
+    /// # assert_eq!(result, expected);
+    /// ```
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        self.align(sent).into_iter().map(|(_, s)| s).collect()
+    }
+}
+impl NLTKWordTokenizer {
+    /// Initialize a [NLTKWordTokenizer] that leaves parentheses and brackets as is
+    pub const fn new() -> Self {
+        Self {
+            convert_parentheses: false,
+        }
+    }
+
+    /// Sets if parentheses and brackets are converted to the PTB symbols
+    /// (``(`` to ``-LRB-``, ``]`` to ``-RSB-``, etc).
+    ///
+    /// Defaults to 'FALSE' - brackets are left as is
+    pub fn set_convert_parentheses(mut self, yes: bool) -> Self {
+        self.convert_parentheses = yes;
+        self
+    }
+
+    /// Runs the regex rules over the text, returning the munged tokens
+    fn munge(&self, text: &str) -> Vec<String> {
+        let mut text = text.to_string();
+
+        for (regexp, substitution) in STARTING_QUOTES.iter() {
+            text = regexp.replace_all(&text, *substitution).into_owned();
+        }
+        text = STARTING_CLITIC
+            .replace_all(&text, |caps: &Captures| {
+                match caps[2].to_lowercase().as_str() {
+                    "m" | "t" | "s" | "d" | "n" => caps[0].to_string(),
+                    _ => format!("{} {}", &caps[1], &caps[2]),
+                }
+            })
+            .into_owned();
+
+        for (regexp, substitution) in PUNCTUATION.iter() {
+            text = regexp.replace_all(&text, *substitution).into_owned();
+        }
+
+        // Handles parentheses.
+        text = PARENS_BRACKETS.replace_all(&text, " $0 ").into_owned();
+        // Optionally convert parentheses
+        if self.convert_parentheses {
+            for (bracket, symbol) in CONVERT_PARENTHESES {
+                text = text.replace(bracket, symbol);
+            }
+        }
+
+        // Handles double dash.
+        text = DOUBLE_DASHES.replace_all(&text, " -- ").into_owned();
+
+        // add extra space to make things easier
+        text = format!(" {} ", text);
+
+        for (regexp, substitution) in ENDING_QUOTES.iter() {
+            text = regexp.replace_all(&text, *substitution).into_owned();
+        }
+
+        for regexp in CONTRACTIONS2.iter().chain(CONTRACTIONS3.iter()) {
+            text = regexp.replace_all(&text, " $1 $2 ").into_owned();
+        }
+
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    /// Returns the spans of the tokens in *text*, as [TokenizerI::span_tokenize],
+    /// or [TokenizeError::MismatchError] if a token returned by the rules isn't found in it.
+    pub fn try_span_tokenize(&self, text: &str) -> Result<Vec<Token>, TokenizeError> {
+        align_tokens(&self.munge(text), text, &*TREEBANK)
+    }
+
+    /// Lines the munged tokens back up with the input string,
+    /// returning each token along with its span.
+    ///
+    /// The rules only pad the tokens and convert quotes and brackets, so every token
+    /// should be found in the input string; one that isn't is left out.
+    fn align<'a>(&self, sent: &'a str) -> Vec<(&'a str, Token)> {
+        let tokens = self.munge(sent);
+        let spans = align_found_tokens(&tokens, sent, &TREEBANK);
+        tokens
+            .iter()
+            .zip(spans)
+            .filter_map(|(token, span)| {
+                let (start, end) = span?;
+                let original = &sent[start..end];
+                if original == token {
+                    Some((original, (start, end)))
+                } else {
+                    Some((Self::converted(token).unwrap_or(original), (start, end)))
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the symbol the tokenizer converted `token` to, if it is one
+    fn converted(token: &str) -> Option<&'static str> {
+        CONVERTED_QUOTES
+            .iter()
+            .chain(CONVERT_PARENTHESES.iter())
+            .map(|&(_, symbol)| symbol)
+            .find(|symbol| *symbol == token)
     }
 }
+
+#[test]
+fn test_nltk_word_tokenize_contractions() {
+    let tokenizer = NLTKWordTokenizer::new();
+    assert_eq!(
+        tokenizer.tokenize("I cannot go"),
+        vec!["I", "can", "not", "go"]
+    );
+    assert_eq!(
+        tokenizer.tokenize("I'm gonna wanna"),
+        vec!["I", "'m", "gon", "na", "wan", "na"]
+    );
+    assert_eq!(
+        tokenizer.tokenize("The 'v', I've been fooled but I'll seek revenge."),
+        vec![
+            "The", "'", "v", "'", ",", "I", "'ve", "been", "fooled", "but", "I", "'ll", "seek",
+            "revenge", "."
+        ]
+    );
+    assert_eq!(
+        tokenizer.tokenize("'v' 're'"),
+        vec!["'", "v", "'", "'re", "'"]
+    );
+}
+
+#[test]
+fn test_nltk_word_tokenize_quotes() {
+    let tokenizer = NLTKWordTokenizer::new();
+    let sentence = "The DUP is similar to the \"religious right\" in the United States and takes a ``hardline'' stance on social issues";
+    let expected = vec![
+        (0, 3),
+        (4, 7),
+        (8, 10),
+        (11, 18),
+        (19, 21),
+        (22, 25),
+        (26, 27),
+        (27, 36),
+        (37, 42),
+        (42, 43),
+        (44, 46),
+        (47, 50),
+        (51, 57),
+        (58, 64),
+        (65, 68),
+        (69, 74),
+        (75, 76),
+        (77, 79),
+        (79, 87),
+        (87, 89),
+        (90, 96),
+        (97, 99),
+        (100, 106),
+        (107, 113),
+    ];
+    assert_eq!(tokenizer.span_tokenize(sentence), expected);
+    let tokens = tokenizer.tokenize(sentence);
+    assert_eq!(&tokens[6..10], &["``", "religious", "right", "''"]);
+}
+
+#[test]
+fn test_nltk_word_tokenize_convert_parentheses() {
+    let tokenizer = NLTKWordTokenizer::new().set_convert_parentheses(true);
+    let sentence = "Please (buy) me [two]";
+    assert_eq!(
+        tokenizer.tokenize(sentence),
+        vec!["Please", "-LRB-", "buy", "-RRB-", "me", "-LSB-", "two", "-RSB-"]
+    );
+    assert_eq!(
+        tokenizer.span_tokenize(sentence),
+        vec![
+            (0, 6),
+            (7, 8),
+            (8, 11),
+            (11, 12),
+            (13, 15),
+            (16, 17),
+            (17, 20),
+            (20, 21)
+        ]
+    );
+
+    // every token of the rules is kept
+    let sentence = "\"Hi\" -- (he said) ''wanna'' go... [ok]?";
+    let munged = tokenizer.munge(sentence);
+    assert_eq!(tokenizer.tokenize(sentence), munged);
+    assert_eq!(
+        tokenizer.try_span_tokenize(sentence).unwrap(),
+        tokenizer.span_tokenize(sentence)
+    );
+}
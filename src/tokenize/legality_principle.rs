@@ -33,66 +33,138 @@
 //!   In HLT-NAACL. pp. 308-316.
 //! - Christopher Hench. 2017. Resonances in Middle High German: New Methodologies in Prosody. UC Berkeley.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use unicode_segmentation::UnicodeSegmentation;
 
 use super::api::TokenizerI;
+use super::util::Token;
 
 // The default vowels for in the english language
 const VOWELS: &str = "aeiouy";
 
 /// Syllabifies words based on the Legality Principle and Onset Maximization.
+///
+/// Spans are the byte offsets of the syllables within the input word.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::legality_principle::LegalitySyllableTokenizer;
+/// let source_text = ["this", "is", "a", "foo", "bar", "like", "sentence", "ten", "test", "case"];
+/// let lp = LegalitySyllableTokenizer::new(&source_text, None, None);
+/// let result = lp.tokenize("bartender");
+/// # assert_eq!(result, vec!["bar", "tend", "er"]);
+/// let result = lp.span_tokenize("Sentence");
+/// # assert_eq!(result, vec![(0, 3), (3, 6), (6, 8)]);
+/// ```
 #[derive(Debug)]
 pub struct LegalitySyllableTokenizer<'a> {
-    source_text: Vec<&'a str>,
     vowels: &'a str,
     threshold: f32,
+    legal_onsets: HashSet<String>,
 }
-impl<'a> TokenizerI<'a> for LegalitySyllableTokenizer<'_> {
+impl TokenizerI for LegalitySyllableTokenizer<'_> {
     /// Apply the Legality Principle in combination with
     /// Onset Maximization to return a list of syllables.
-    fn tokenize(&self, _sent: &'a str) -> Vec<&'a str> {
-        todo!()
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
     }
 
-    fn span_tokenize(&self, _sent: &str) -> Vec<super::util::Token> {
-        todo!()
+    /// Identify the syllables using integer offsets [Token].
+    ///
+    /// The word is scanned from its end, adding consonants to the onset of the
+    /// current syllable for as long as the onset remains legal.
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        let mut result: Vec<Token> = Vec::new();
+        if sent.is_empty() {
+            return result;
+        }
+        let mut end = sent.len();
+        let mut start = sent.len();
+        let mut current_onset = String::new();
+        let (mut vowel, mut onset) = (false, false);
+        for (i, c) in sent.grapheme_indices(true).rev() {
+            let c_lower = c.to_lowercase();
+            if !vowel {
+                start = i;
+                vowel = self.is_vowel(&c_lower);
+            } else if self
+                .legal_onsets
+                .contains(&format!("{}{}", c_lower, current_onset))
+            {
+                start = i;
+                current_onset.insert_str(0, &c_lower);
+                onset = true;
+            } else if self.is_vowel(&c_lower) && !onset {
+                start = i;
+                current_onset.insert_str(0, &c_lower);
+            } else {
+                result.push((start, end));
+                end = start;
+                start = i;
+                current_onset.clear();
+                vowel = self.is_vowel(&c_lower);
+            }
+        }
+        result.push((start, end));
+        result.reverse();
+        result
     }
 }
 
 impl<'a> LegalitySyllableTokenizer<'a> {
     /// Gathers all onsets and then return only those above the frequency threshold
-    pub fn find_legal_onsets(self, _words: Vec<&str>) -> HashMap<&str, usize> {
-        todo!()
+    ///
+    /// The onsets are lower cased, words starting with a vowel count towards
+    /// the total number of words but don't have an onset.
+    pub fn find_legal_onsets(&self, words: &[&str]) -> HashSet<String> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for onset in words.iter().filter_map(|word| self.onset(word)) {
+            *counts.entry(onset.to_lowercase()).or_insert(0) += 1;
+        }
+        counts
+            .into_iter()
+            .filter(|(_, count)| *count as f32 / words.len() as f32 > self.threshold)
+            .map(|(onset, _)| onset)
+            .collect()
     }
 
     /// Returns consonant cluster of word, i.e. all characters until the first vowel.
     /// If the word starts with a vowel, will return [None]
-    pub fn onset(self, word: &str) -> Option<&str> {
-        let mut index: Option<usize> = None;
-        for (i, c) in word.grapheme_indices(true) {
-            if self.vowels.contains(c) {
-                break;
-            } else {
-                index = i.into();
-            }
-        }
+    pub fn onset<'w>(&self, word: &'w str) -> Option<&'w str> {
+        let index = word
+            .grapheme_indices(true)
+            .find(|(_, c)| self.is_vowel(&c.to_lowercase()))
+            .map_or(word.len(), |(i, _)| i);
 
-        if let Some(..) = index {
-            let (result, _) = word.split_at(index.unwrap());
-            Some(result)
+        if index > 0 {
+            Some(&word[..index])
         } else {
             None
         }
     }
 
+    /// Returns the legal onsets found in the source text
+    pub fn legal_onsets(&self) -> &HashSet<String> {
+        &self.legal_onsets
+    }
+
+    fn is_vowel(&self, c: &str) -> bool {
+        self.vowels.contains(c)
+    }
+
     /// Initializes an instance of the [LegalitySyllableTokenizer] struct
     /// tokenized_source_text: List of valid tokens in the language
     /// language_vowels: Valid vowels in language or IPA representation, defaults to "aeiouy"
     /// legal_frequency_threshold: Lowest frequency of all onsets to be considered a legal onset, defaults to 0.001
     pub fn new<P1, P2>(
-        tokenized_source_text: &[&'a str],
+        tokenized_source_text: &[&str],
         language_vowels: P1,
         legal_frequency_threshold: P2,
     ) -> Self
@@ -100,10 +172,24 @@ impl<'a> LegalitySyllableTokenizer<'a> {
         P1: Into<Option<&'a str>>,
         P2: Into<Option<f32>>,
     {
-        Self {
-            source_text: Vec::from(tokenized_source_text),
+        let mut tokenizer = Self {
             vowels: language_vowels.into().unwrap_or(VOWELS),
             threshold: legal_frequency_threshold.into().unwrap_or(0.001),
-        }
+            legal_onsets: HashSet::new(),
+        };
+        tokenizer.legal_onsets = tokenizer.find_legal_onsets(tokenized_source_text);
+        tokenizer
     }
 }
+
+#[test]
+fn test_legality_principle_onsets() {
+    let source_text = ["strong", "Play", "apple", "tree", "spring", "string"];
+    let lp = LegalitySyllableTokenizer::new(&source_text, None, 0.1);
+    assert_eq!(lp.onset("Strong"), Some("Str"));
+    assert_eq!(lp.onset("apple"), None);
+    assert_eq!(lp.onset("rhythm"), Some("rh"));
+    let mut onsets: Vec<_> = lp.legal_onsets().iter().map(String::as_str).collect();
+    onsets.sort_unstable();
+    assert_eq!(onsets, vec!["pl", "spr", "str", "tr"]);
+}
@@ -0,0 +1,194 @@
+//! Lossless Tokenization
+//!
+//! A ``LosslessTokenizer`` keeps the text between the tokens of another tokenizer,
+//! returning every part of the text as a typed [Piece], so that the text is rebuilt
+//! byte-for-byte by concatenating the pieces:
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::lossless::{LosslessTokenizer, PieceKind};
+//! # use lib_nltk::tokenize::regexp::WordPunctTokenizer;
+//! let text = "Good muffins  cost $3.88\n\tin New York.";
+//! let tokenizer = LosslessTokenizer::new(WordPunctTokenizer());
+//! let pieces = tokenizer.pieces(text);
+//! let rebuilt: String = pieces.iter().map(|piece| piece.text).collect();
+//! assert_eq!(rebuilt, text);
+//!
+//! // Upper case the words, leaving everything else untouched
+//! let result: String = pieces
+//!     .iter()
+//!     .map(|piece| match piece.kind {
+//!         PieceKind::Word => piece.text.to_uppercase(),
+//!         _ => piece.text.to_string(),
+//!     })
+//!     .collect();
+//! # assert_eq!(result, "GOOD MUFFINS  COST $3.88\n\tIN NEW YORK.");
+//! ```
+
+use super::api::TokenizerI;
+use super::util::Token;
+
+/// The kinds of the pieces of a text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PieceKind {
+    /// A token with a letter or a digit
+    Word,
+    /// A token without letters or digits
+    Punctuation,
+    /// Whitespace between the tokens, or a token of whitespace only
+    Whitespace,
+    /// Text other than whitespace between the tokens, dropped by the tokenizer
+    /// (the separators of a tokenizer splitting on them)
+    Separator,
+}
+
+/// A part of the text, with its kind and its span in the text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece<'a> {
+    /// The kind of the piece
+    pub kind: PieceKind,
+    /// The text of the piece, ``&text[span.0..span.1]``
+    pub text: &'a str,
+    /// The byte offsets of the piece in the text
+    pub span: Token,
+}
+
+/// A tokenizer wrapping another tokenizer, returning its tokens along with the text
+/// between them, so that no part of the text is lost.
+///
+/// The pieces are in the order of the text, cover all of it and don't overlap:
+/// a token overlapping the previous one is cut to start where that one ends,
+/// and empty tokens are dropped.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::lossless::{LosslessTokenizer, PieceKind};
+/// # use lib_nltk::tokenize::regexp::RegexpTokenizer;
+/// let tokenizer = LosslessTokenizer::new(RegexpTokenizer::builder(r"\s*;\s*").set_gaps(true).build().unwrap());
+/// let result: Vec<_> = tokenizer.pieces("a; b ;c").into_iter().map(|piece| (piece.kind, piece.text)).collect();
+/// # assert_eq!(result, vec![(PieceKind::Word, "a"), (PieceKind::Separator, ";"), (PieceKind::Whitespace, " "),
+/// #     (PieceKind::Word, "b"), (PieceKind::Whitespace, " "), (PieceKind::Separator, ";"), (PieceKind::Word, "c")]);
+/// let result = tokenizer.tokenize("a; b");
+/// # assert_eq!(result, vec!["a", ";", " ", "b"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LosslessTokenizer<T> {
+    tokenizer: T,
+}
+
+impl<T: TokenizerI> LosslessTokenizer<T> {
+    /// Initializes a [LosslessTokenizer] keeping the text between the tokens of *tokenizer*
+    pub fn new(tokenizer: T) -> Self {
+        Self { tokenizer }
+    }
+
+    /// Returns the wrapped tokenizer
+    pub fn inner(&self) -> &T {
+        &self.tokenizer
+    }
+
+    /// Returns the pieces of *text*: the tokens, and the whitespace and separators between them
+    pub fn pieces<'a>(&self, text: &'a str) -> Vec<Piece<'a>> {
+        let mut spans = self.tokenizer.span_tokenize(text);
+        spans.sort_unstable();
+
+        let mut result = Vec::new();
+        let mut offset = 0;
+        for (start, end) in spans {
+            let start = start.max(offset);
+            if start >= end {
+                continue;
+            }
+            push_gap(&mut result, text, offset, start);
+            let token = &text[start..end];
+            let kind = if token.chars().any(char::is_alphanumeric) {
+                PieceKind::Word
+            } else if token.chars().all(char::is_whitespace) {
+                PieceKind::Whitespace
+            } else {
+                PieceKind::Punctuation
+            };
+            result.push(Piece {
+                kind,
+                text: token,
+                span: (start, end),
+            });
+            offset = end;
+        }
+        push_gap(&mut result, text, offset, text.len());
+        result
+    }
+}
+
+// Pushes the runs of whitespace and of separators between two tokens
+fn push_gap<'a>(pieces: &mut Vec<Piece<'a>>, text: &'a str, start: usize, end: usize) {
+    let mut run_start = start;
+    let mut chars = text[start..end].char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        let whitespace = c.is_whitespace();
+        let run_end = match chars.peek() {
+            Some(&(_, next)) if next.is_whitespace() == whitespace => continue,
+            Some(&(i, _)) => start + i,
+            None => end,
+        };
+        pieces.push(Piece {
+            kind: if whitespace {
+                PieceKind::Whitespace
+            } else {
+                PieceKind::Separator
+            },
+            text: &text[run_start..run_end],
+            span: (run_start, run_end),
+        });
+        run_start = run_end;
+    }
+}
+
+impl<T: TokenizerI> TokenizerI for LosslessTokenizer<T> {
+    /// Returns the text of all the pieces of *sent*, see [LosslessTokenizer::pieces]
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.pieces(sent)
+            .into_iter()
+            .map(|piece| piece.text)
+            .collect()
+    }
+
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        self.pieces(sent)
+            .into_iter()
+            .map(|piece| piece.span)
+            .collect()
+    }
+}
+
+#[test]
+fn test_lossless_pieces() {
+    use super::destructive::NLTKWordTokenizer;
+    use super::regexp::WhitespaceTokenizer;
+    use super::simple::SpaceTokenizer;
+
+    let text = "  They said: \"don't  stop\"\r\n\n(ok)…\t";
+    let rebuilt = |pieces: Vec<Piece>| pieces.iter().map(|piece| piece.text).collect::<String>();
+    assert_eq!(
+        rebuilt(LosslessTokenizer::new(SpaceTokenizer).pieces(text)),
+        text
+    );
+    assert_eq!(
+        rebuilt(LosslessTokenizer::new(WhitespaceTokenizer()).pieces(text)),
+        text
+    );
+    let pieces = LosslessTokenizer::new(NLTKWordTokenizer::new()).pieces(text);
+    assert_eq!(rebuilt(pieces.clone()), text);
+    assert!(pieces
+        .windows(2)
+        .all(|pair| pair[0].span.1 == pair[1].span.0));
+    assert_eq!(pieces[0].kind, PieceKind::Whitespace);
+    assert!(pieces
+        .iter()
+        .any(|piece| piece.kind == PieceKind::Punctuation && piece.text == "…"));
+
+    assert!(LosslessTokenizer::new(SpaceTokenizer).pieces("").is_empty());
+}
@@ -2,11 +2,178 @@
 //!
 //! Tokenizers divide strings into lists of substrings.  For example,
 //! tokenizers can be used to find the words and punctuation in a string:
-//pub mod api;
-mod api;
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::word_tokenize;
+//! let s = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\n\nThanks.";
+//! let result = word_tokenize(s, None, None).unwrap();
+//! # assert_eq!(result, vec!["Good", "muffins", "cost", "$", "3.88", "in", "New", "York", ".",
+//! #     "Please", "buy", "me", "two", "of", "them", ".", "Thanks", "."]);
+//! ```
+pub mod api;
+pub mod bpe;
+pub mod casual;
+pub mod cjk;
 pub mod destructive;
 pub mod legality_principle;
+pub mod lossless;
+pub mod moses;
+pub mod mwe;
+pub mod pipeline;
+pub mod punkt;
+pub mod recognizer;
 pub mod regexp;
+pub mod registry;
 pub mod sexpr;
 pub mod simple;
+pub mod sonority;
+pub mod stream;
+pub mod texttiling;
+pub mod toktok;
+pub mod treebank;
+pub mod unigram;
 pub mod util;
+
+use lazy_static::lazy_static;
+
+use api::TokenizerI;
+use destructive::NLTKWordTokenizer;
+use punkt::PunktSentenceTokenizer;
+use util::{Token, TokenizeError};
+
+lazy_static! {
+    static ref ENGLISH_SENT_TOKENIZER: PunktSentenceTokenizer = PunktSentenceTokenizer::english();
+}
+
+// Standard word tokenizer.
+const TREEBANK_WORD_TOKENIZER: NLTKWordTokenizer = NLTKWordTokenizer::new();
+
+// Returns the Punkt tokenizer for the given language, if one is built in.
+fn sent_tokenizer(language: &str) -> Result<&'static PunktSentenceTokenizer, TokenizeError> {
+    match language {
+        "english" => Ok(&ENGLISH_SENT_TOKENIZER),
+        _ => Err(TokenizeError::UnsupportedLanguage(language.to_string())),
+    }
+}
+
+/// Return a sentence-tokenized copy of *text*,
+/// using NLTK's recommended sentence tokenizer
+/// (currently [PunktSentenceTokenizer] for the specified language).
+///
+/// - language: the model name in the Punkt corpus, defaults to "english"
+///
+/// Returns [TokenizeError::UnsupportedLanguage] if there's no tokenizer for the language.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::sent_tokenize;
+/// let s = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\n\nThanks.";
+/// let result = sent_tokenize(s, "english").unwrap();
+/// # assert_eq!(result, vec!["Good muffins cost $3.88\nin New York.", "Please buy me\ntwo of them.", "Thanks."]);
+/// ```
+pub fn sent_tokenize<'a, L>(text: &'a str, language: L) -> Result<Vec<&'a str>, TokenizeError>
+where
+    L: Into<Option<&'a str>>,
+{
+    Ok(sent_tokenizer(language.into().unwrap_or("english"))?.tokenize(text))
+}
+
+/// Return the spans of the sentences in *text*, the same sentences returned by [sent_tokenize].
+///
+/// The spans are the byte offsets of the sentences in *text*.
+pub fn sent_span_tokenize<'a, L>(text: &str, language: L) -> Result<Vec<Token>, TokenizeError>
+where
+    L: Into<Option<&'a str>>,
+{
+    Ok(sent_tokenizer(language.into().unwrap_or("english"))?.span_tokenize(text))
+}
+
+/// Return a tokenized copy of *text*,
+/// using NLTK's recommended word tokenizer
+/// (currently an improved [NLTKWordTokenizer]
+/// along with [PunktSentenceTokenizer]
+/// for the specified language).
+///
+/// - language: the model name in the Punkt corpus, defaults to "english"
+/// - preserve_line: A flag to decide whether to sentence tokenize the text or not, defaults to false
+///
+/// Returns [TokenizeError::UnsupportedLanguage] if there's no tokenizer for the language.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::word_tokenize;
+/// let result = word_tokenize("I cannot go. Not today.", "english", false).unwrap();
+/// # assert_eq!(result, vec!["I", "can", "not", "go", ".", "Not", "today", "."]);
+/// let result = word_tokenize("I cannot go. Not today.", None, true).unwrap();
+/// # assert_eq!(result, vec!["I", "can", "not", "go.", "Not", "today", "."]);
+/// ```
+pub fn word_tokenize<'a, L, P>(
+    text: &'a str,
+    language: L,
+    preserve_line: P,
+) -> Result<Vec<&'a str>, TokenizeError>
+where
+    L: Into<Option<&'a str>>,
+    P: Into<Option<bool>>,
+{
+    let sentences = if preserve_line.into().unwrap_or(false) {
+        vec![text]
+    } else {
+        sent_tokenize(text, language)?
+    };
+    Ok(TREEBANK_WORD_TOKENIZER
+        .tokenize_sents(&sentences)
+        .into_iter()
+        .flatten()
+        .collect())
+}
+
+/// Return the spans of the tokens in *text*, the same tokens returned by [word_tokenize].
+///
+/// The spans are the byte offsets of the tokens in *text*, rather than within their sentence.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::word_span_tokenize;
+/// let result = word_span_tokenize("I cannot go. Not today.", None, None).unwrap();
+/// # assert_eq!(result, vec![(0, 1), (2, 5), (5, 8), (9, 11), (11, 12), (13, 16), (17, 22), (22, 23)]);
+/// ```
+pub fn word_span_tokenize<'a, L, P>(
+    text: &str,
+    language: L,
+    preserve_line: P,
+) -> Result<Vec<Token>, TokenizeError>
+where
+    L: Into<Option<&'a str>>,
+    P: Into<Option<bool>>,
+{
+    let sentences = if preserve_line.into().unwrap_or(false) {
+        vec![(0, text.len())]
+    } else {
+        sent_span_tokenize(text, language)?
+    };
+    Ok(sentences
+        .into_iter()
+        .flat_map(|(start, end)| {
+            TREEBANK_WORD_TOKENIZER
+                .span_tokenize(&text[start..end])
+                .into_iter()
+                .map(move |(s, e)| (start + s, start + e))
+        })
+        .collect())
+}
+
+#[test]
+fn test_unsupported_language() {
+    assert!(matches!(
+        word_tokenize("Hallo Welt.", "klingon", None),
+        Err(TokenizeError::UnsupportedLanguage(_))
+    ));
+}
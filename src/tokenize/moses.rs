@@ -0,0 +1,781 @@
+//! Moses Tokenizer and Detokenizer
+//!
+//! A port of the ``tokenizer.perl`` and ``detokenizer.perl`` scripts of the
+//! Moses statistical machine translation toolkit (by way of sacremoses).
+//! The tokens are those Moses would write out, so the output of
+//! [MosesTokenizer::tokenize] joined with single spaces is the line the script
+//! produces for the same input line.
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::moses::{MosesDetokenizer, MosesTokenizer};
+//! let tokenizer = MosesTokenizer::new("en").unwrap();
+//! let tokens = tokenizer.tokenize("This, is a sentence with weird» symbols… appearing everywhere¿");
+//! # assert_eq!(tokens, vec!["This", ",", "is", "a", "sentence", "with", "weird", "»", "symbols", "…", "appearing", "everywhere", "¿"]);
+//! let detokenizer = MosesDetokenizer::new("en");
+//! let text = detokenizer.detokenize(&tokens);
+//! # assert_eq!(text, "This, is a sentence with weird » symbols … appearing everywhere ¿");
+//! ```
+//!
+//! Copyright (C) 2001-2022 NLTK Project
+//! Author: Liling Tan
+//! URL: <https://www.nltk.org>
+//! For license information, see LICENSE.TXT
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead};
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+use super::util::{is_cjk, xml_escape, xml_unescape, TokenizeError};
+
+// The Perl IsAlnum and IsAlpha character sets, with the Indic viramas and nuktas added
+// so that the conjuncts aren't split apart.
+macro_rules! alpha {
+    () => {
+        r"\p{Alphabetic}\x{093C}\x{094D}\x{09BC}\x{09CD}\x{0A3C}\x{0A4D}\x{0ABC}\x{0ACD}\x{0B3C}\x{0B4D}\x{0BCD}\x{0C4D}\x{0CBC}\x{0CCD}\x{0D4D}\x{0DCA}"
+    };
+}
+macro_rules! alnum {
+    () => {
+        concat!(alpha!(), r"\p{Nd}")
+    };
+}
+
+lazy_static! {
+    // Remove ASCII junk.
+    static ref DEDUPLICATE_SPACE: (Regex, &'static str) = (Regex::new(r"\s+").unwrap(), " ");
+    static ref ASCII_JUNK: (Regex, &'static str) = (Regex::new(r"[\x00-\x1F]").unwrap(), "");
+
+    // Pad all "other" special characters not in IsAlnum.
+    static ref PAD_NOT_ISALNUM: (Regex, &'static str) =
+        (Regex::new(concat!(r"([^", alnum!(), r"\s\.'`,\-])")).unwrap(), " $1 ");
+
+    // Splits all hyphens (regardless of circumstances), e.g.
+    // 'foo-bar' -> 'foo @-@ bar'
+    // the hyphen has to be followed by an alphanumeric character too, which is
+    // checked by `MosesTokenizer::tokenize` as there are no lookaheads.
+    static ref AGGRESSIVE_HYPHEN_SPLIT: (Regex, &'static str) =
+        (Regex::new(concat!(r"([", alnum!(), r"])\-")).unwrap(), "$1 @-@ ");
+    static ref ALNUM_START: Regex = Regex::new(concat!(r"^[", alnum!(), r"]")).unwrap();
+
+    // Make multi-dots stay together.
+    static ref REPLACE_DOT_WITH_LITERALSTRING_1: (Regex, &'static str) =
+        (Regex::new(r"\.([\.]+)").unwrap(), " DOTMULTI$1");
+    static ref REPLACE_DOT_WITH_LITERALSTRING_2: (Regex, &'static str) =
+        (Regex::new(r"DOTMULTI\.([^\.])").unwrap(), "DOTDOTMULTI $1");
+    static ref REPLACE_DOT_WITH_LITERALSTRING_3: (Regex, &'static str) =
+        (Regex::new(r"DOTMULTI\.").unwrap(), "DOTDOTMULTI");
+
+    // Separate out "," except if within numbers (5,300)
+    // e.g.  A,B,C,D,E > A , B,C , D,E
+    // First application uses up B so rule can't see B,C
+    // two-step version here may create extra spaces but these are removed later
+    // will also space digit,letter or letter,digit forms (redundant with next section)
+    static ref COMMA_SEPARATE_1: (Regex, &'static str) =
+        (Regex::new(r"([^\p{N}])[,]").unwrap(), "$1 , ");
+    static ref COMMA_SEPARATE_2: (Regex, &'static str) =
+        (Regex::new(r"[,]([^\p{N}])").unwrap(), " , $1");
+    static ref COMMA_SEPARATE_3: (Regex, &'static str) =
+        (Regex::new(r"([\p{N}])[,]$").unwrap(), "$1 , ");
+
+    static ref ENGLISH_SPECIFIC_APOSTROPHE: [(Regex, &'static str); 5] = [
+        (Regex::new(concat!(r"([^", alpha!(), r"])[']([^", alpha!(), r"])")).unwrap(), "$1 ' $2"),
+        (Regex::new(concat!(r"([^", alpha!(), r"\p{N}])[']([", alpha!(), r"])")).unwrap(), "$1 ' $2"),
+        (Regex::new(concat!(r"([", alpha!(), r"])[']([^", alpha!(), r"])")).unwrap(), "$1 ' $2"),
+        (Regex::new(concat!(r"([", alpha!(), r"])[']([", alpha!(), r"])")).unwrap(), "$1 '$2"),
+        (Regex::new(r"([\p{N}])[']([s])").unwrap(), "$1 '$2"),
+    ];
+
+    static ref FR_IT_SPECIFIC_APOSTROPHE: [(Regex, &'static str); 4] = [
+        (Regex::new(concat!(r"([^", alpha!(), r"])[']([^", alpha!(), r"])")).unwrap(), "$1 ' $2"),
+        (Regex::new(concat!(r"([^", alpha!(), r"])[']([", alpha!(), r"])")).unwrap(), "$1 ' $2"),
+        (Regex::new(concat!(r"([", alpha!(), r"])[']([^", alpha!(), r"])")).unwrap(), "$1 ' $2"),
+        (Regex::new(concat!(r"([", alpha!(), r"])[']([", alpha!(), r"])")).unwrap(), "$1' $2"),
+    ];
+
+    static ref NON_SPECIFIC_APOSTROPHE: (Regex, &'static str) = (Regex::new(r"'").unwrap(), " ' ");
+
+    static ref TRAILING_DOT_APOSTROPHE: (Regex, &'static str) =
+        (Regex::new(r"\.' ?$").unwrap(), " . ' ");
+
+    static ref ENDS_WITH_PERIOD: Regex = Regex::new(r"^(\S+)\.$").unwrap();
+    static ref STARTS_WITH_DIGITS: Regex = Regex::new(r"^[0-9]+").unwrap();
+
+    // Detokenize the aggressive hyphen split.
+    static ref AGGRESSIVE_HYPHEN_JOIN: (Regex, &'static str) = (Regex::new(r" @\-@ ").unwrap(), "-");
+    static ref ONE_SPACE: (Regex, &'static str) = (Regex::new(r" {2,}").unwrap(), " ");
+
+    static ref RIGHT_SHIFT_PUNCT: Regex = Regex::new(r"^[\p{Sc}\(\[\{¿¡]+$").unwrap();
+    static ref LEFT_SHIFT_PUNCT: Regex = Regex::new(r"^[,\.\?!:;\\%\}\]\)]+$").unwrap();
+    static ref FRENCH_SPACED_PUNCT: Regex = Regex::new(r"^[\?!:;\\%]$").unwrap();
+    static ref ENGLISH_CONTRACTION: Regex = Regex::new(concat!(r"^'[", alpha!(), r"]")).unwrap();
+    static ref ELISION: Regex = Regex::new(concat!(r"[", alpha!(), r"]'$")).unwrap();
+    static ref ALPHA_END: Regex = Regex::new(concat!(r"[", alpha!(), r"]$")).unwrap();
+    static ref ALPHA_START: Regex = Regex::new(concat!(r"^[", alpha!(), r"]")).unwrap();
+    static ref NUMBER: Regex = Regex::new(r"^[0-9]+$").unwrap();
+    static ref DECIMAL_POINT: Regex = Regex::new(r"^[.,]$").unwrap();
+    static ref CZECH_DASH: Regex = Regex::new(r"^[-–]$").unwrap();
+    static ref CZECH_DASHED_WORD: Regex = Regex::new(r"(?i)^li$|^mail.*").unwrap();
+    static ref QUOTES: Regex = Regex::new(r#"^['"„“`]+$"#).unwrap();
+    static ref DOUBLE_QUOTES: Regex = Regex::new(r"^[„“”]+$").unwrap();
+    static ref FINNISH_CASE_SUFFIX: Regex = Regex::new(concat!(
+        r"^(N|n|A|a|Ä|ä|ssa|Ssa|ssä|Ssä|sta|stä|Sta|Stä|hun|Hun|hyn|Hyn|han|Han|hän|Hän|hön|Hön|",
+        r"un|Un|yn|Yn|an|An|än|Än|ön|Ön|seen|Seen|lla|Lla|llä|Llä|lta|Lta|ltä|Ltä|lle|Lle|ksi|Ksi|",
+        r"kse|Kse|tta|Tta|ine|Ine)(ni|si|mme|nne|nsa)?(ko|kö|han|hän|pa|pä|kaan|kään|kin)?$"
+    ))
+    .unwrap();
+}
+
+// The nonbreaking_prefix.en file of Moses
+const ENGLISH_NONBREAKING_PREFIXES: &str = "\
+#Anything in this file, followed by a period (and an upper-case word), does NOT indicate an end-of-sentence marker.
+#Special cases are included for prefixes that ONLY appear before 0-9 numbers.
+
+#any single upper case letter  followed by a period is not a sentence ender (excluding I occasionally, but we leave it in)
+#usually upper case letters are initials in a name
+A
+B
+C
+D
+E
+F
+G
+H
+I
+J
+K
+L
+M
+N
+O
+P
+Q
+R
+S
+T
+U
+V
+W
+X
+Y
+Z
+
+#List of titles. These are often followed by upper-case names, but do not indicate sentence breaks
+Adj
+Adm
+Adv
+Asst
+Bart
+Bldg
+Brig
+Bros
+Capt
+Cmdr
+Col
+Comdr
+Con
+Corp
+Cpl
+DR
+Dr
+Drs
+Ens
+Gen
+Gov
+Hon
+Hr
+Hosp
+Insp
+Lt
+MM
+MR
+MRS
+MS
+Maj
+Messrs
+Mlle
+Mme
+Mr
+Mrs
+Ms
+Msgr
+Op
+Ord
+Pfc
+Ph
+Prof
+Pvt
+Rep
+Reps
+Res
+Rev
+Rt
+Sen
+Sens
+Sfc
+Sgt
+Sr
+St
+Supt
+Surg
+
+#misc - odd period-ending items that NEVER indicate breaks (p.m. does NOT fall into this category - it sometimes ends a sentence)
+v
+vs
+i.e
+rev
+e.g
+
+#Numbers only. These should only induce breaks when followed by a numeric sequence
+# add NUMERIC_ONLY after the word for this function
+#This case is mostly for the english \"No.\" which can either be a sentence of its own, or
+#if followed by a number, a non-breaking prefix
+No #NUMERIC_ONLY#
+Nos
+Art #NUMERIC_ONLY#
+Nr
+pp #NUMERIC_ONLY#
+
+#month abbreviations
+Jan
+Feb
+Mar
+Apr
+#May is a full word
+Jun
+Jul
+Aug
+Sep
+Oct
+Nov
+Dec
+";
+
+// Substitutes every match of the rule.
+fn replace(text: &str, rule: &(Regex, &str)) -> String {
+    let (regexp, substitution) = rule;
+    regexp.replace_all(text, *substitution).into_owned()
+}
+
+/// The Moses tokenizer, splitting a line of text into the tokens written out
+/// by ``tokenizer.perl``.
+///
+/// The language selects the apostrophe rules (English, French and Italian have
+/// their own) and the nonbreaking prefixes, words such as "Mr" whose final period
+/// isn't split off. Only the English prefixes are built in, the tokenizer of
+/// another language is initialized with the Moses ``nonbreaking_prefix.*`` file of
+/// the language, see [MosesTokenizer::with_nonbreaking_prefixes].
+///
+/// By default the XML special characters are escaped (`&` is returned as `&amp;`,
+/// `|` as `&#124;` and so on), see [xml_escape].
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::moses::MosesTokenizer;
+/// let tokenizer = MosesTokenizer::new("en").unwrap().set_aggressive_dash_splits(true);
+/// let tokens = tokenizer.tokenize("Mr. Smith's self-driving car costs $5,300 (A&B).");
+/// # assert_eq!(tokens, vec!["Mr.", "Smith", "&apos;s", "self", "@-@", "driving", "car", "costs", "$", "5,300", "(", "A", "&amp;", "B", ")", "."]);
+/// let prefixes = "M\nMme\nMlle\n";
+/// let tokenizer = MosesTokenizer::with_nonbreaking_prefixes("fr", prefixes.as_bytes())
+///     .unwrap()
+///     .set_escape(false);
+/// let tokens = tokenizer.tokenize("L'amour de l'art...");
+/// # assert_eq!(tokens, vec!["L'", "amour", "de", "l'", "art", "..."]);
+/// ```
+#[derive(Debug)]
+pub struct MosesTokenizer {
+    lang: String,
+    nonbreaking_prefixes: HashSet<String>,
+    numeric_only_prefixes: HashSet<String>,
+    aggressive_dash_splits: bool,
+    escape: bool,
+}
+
+impl Default for MosesTokenizer {
+    fn default() -> Self {
+        Self::new("en").unwrap()
+    }
+}
+
+impl MosesTokenizer {
+    /// Initializes an instance of the [MosesTokenizer] struct for the language
+    /// *lang*, given by its ISO 639-1 code ("en", "fr", ...), with its built-in
+    /// nonbreaking prefixes.
+    ///
+    /// Returns [TokenizeError::UnsupportedLanguage] for a language without built-in
+    /// prefixes, that is any language but English.
+    pub fn new(lang: &str) -> Result<Self, TokenizeError> {
+        match lang {
+            "en" => Ok(Self::with_nonbreaking_prefixes(
+                lang,
+                ENGLISH_NONBREAKING_PREFIXES.as_bytes(),
+            )
+            .unwrap()),
+            _ => Err(TokenizeError::UnsupportedLanguage(lang.to_string())),
+        }
+    }
+
+    /// Initializes an instance of the [MosesTokenizer] struct for the language
+    /// *lang*, with the nonbreaking prefixes read from a Moses ``nonbreaking_prefix.*``
+    /// file (see [MosesTokenizer::load_nonbreaking_prefixes]).
+    pub fn with_nonbreaking_prefixes<R: BufRead>(lang: &str, reader: R) -> io::Result<Self> {
+        let mut tokenizer = Self {
+            lang: lang.to_string(),
+            nonbreaking_prefixes: HashSet::new(),
+            numeric_only_prefixes: HashSet::new(),
+            aggressive_dash_splits: false,
+            escape: true,
+        };
+        tokenizer.read_nonbreaking_prefixes(reader)?;
+        Ok(tokenizer)
+    }
+
+    /// Replaces the nonbreaking prefixes by the ones read from a Moses
+    /// ``nonbreaking_prefix.*`` file: one prefix per line, `#` starts a comment,
+    /// and a prefix followed by `#NUMERIC_ONLY#` is only nonbreaking before a number.
+    pub fn load_nonbreaking_prefixes<R: BufRead>(mut self, reader: R) -> io::Result<Self> {
+        self.nonbreaking_prefixes.clear();
+        self.numeric_only_prefixes.clear();
+        self.read_nonbreaking_prefixes(reader)?;
+        Ok(self)
+    }
+
+    /// Splits every hyphen between two alphanumeric characters into an `@-@` token.
+    pub fn set_aggressive_dash_splits(mut self, yes: bool) -> Self {
+        self.aggressive_dash_splits = yes;
+        self
+    }
+
+    /// Escapes the XML special characters of the tokens (the default).
+    pub fn set_escape(mut self, yes: bool) -> Self {
+        self.escape = yes;
+        self
+    }
+
+    /// Returns the language of the tokenizer
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    fn read_nonbreaking_prefixes<R: BufRead>(&mut self, reader: R) -> io::Result<()> {
+        for line in reader.lines() {
+            let line = line?;
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once(char::is_whitespace) {
+                Some((prefix, rest)) if rest.trim() == "#NUMERIC_ONLY#" => {
+                    self.numeric_only_prefixes.insert(prefix.to_string());
+                }
+                _ => {
+                    self.nonbreaking_prefixes.insert(line.to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Return the tokens of the line *text*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::moses::MosesTokenizer;
+    /// let tokenizer = MosesTokenizer::default();
+    /// let tokens = tokenizer.tokenize("See No. 5 in the U.S. list... It's <b>bold</b>!");
+    /// # assert_eq!(tokens, vec!["See", "No.", "5", "in", "the", "U.S.", "list", "...", "It", "&apos;s", "&lt;", "b", "&gt;", "bold", "&lt;", "/", "b", "&gt;", "!"]);
+    /// ```
+    pub fn tokenize(&self, text: &str) -> Vec<String> {
+        // De-duplicate spaces and clean ASCII junk
+        let text = replace(text, &DEDUPLICATE_SPACE);
+        let text = replace(&text, &ASCII_JUNK);
+        // Strips heading and trailing spaces.
+        let text = text.trim();
+
+        // Separate special characters outside of IsAlnum character set.
+        let mut text = replace(text, &PAD_NOT_ISALNUM);
+        // Aggressively splits dashes
+        if self.aggressive_dash_splits {
+            let (regexp, substitution) = &*AGGRESSIVE_HYPHEN_SPLIT;
+            text = regexp
+                .replace_all(&text, |caps: &Captures| {
+                    let m = caps.get(0).unwrap();
+                    let mut result = String::new();
+                    if ALNUM_START.is_match(&text[m.end()..]) {
+                        caps.expand(substitution, &mut result);
+                    } else {
+                        result.push_str(m.as_str());
+                    }
+                    result
+                })
+                .into_owned();
+        }
+
+        // Replaces multidots with "DOTDOTMULTI" literal strings.
+        let mut text = replace_multidots(&text);
+
+        // Separate out "," except if within numbers e.g. 5,300
+        for rule in [&*COMMA_SEPARATE_1, &*COMMA_SEPARATE_2, &*COMMA_SEPARATE_3] {
+            text = replace(&text, rule);
+        }
+
+        // (Language-specific) apostrophe tokenization.
+        match self.lang.as_str() {
+            "en" => {
+                for rule in ENGLISH_SPECIFIC_APOSTROPHE.iter() {
+                    text = replace(&text, rule);
+                }
+            }
+            "fr" | "it" => {
+                for rule in FR_IT_SPECIFIC_APOSTROPHE.iter() {
+                    text = replace(&text, rule);
+                }
+            }
+            _ => text = replace(&text, &NON_SPECIFIC_APOSTROPHE),
+        }
+
+        // Handles nonbreaking prefixes.
+        let text = self.handle_nonbreaking_prefixes(&text);
+        // Cleans up extraneous spaces.
+        let text = replace(&text, &DEDUPLICATE_SPACE);
+        // Split trailing ".'".
+        let text = replace(text.trim(), &TRAILING_DOT_APOSTROPHE);
+
+        // Restore multidots.
+        let mut text = restore_multidots(&text);
+        if self.escape {
+            // Escape XML symbols.
+            text = xml_escape(&text);
+        }
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    // Splits the final period off the tokens, unless the token is an abbreviation:
+    // i.   the prefix contains a period and an alphabetic character, or
+    // ii.  the prefix is a nonbreaking prefix, or
+    // iii. the next token starts with a lowercase character, or
+    // iv.  the prefix is numeric only, and the next token starts with a digit.
+    fn handle_nonbreaking_prefixes(&self, text: &str) -> String {
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let mut result = Vec::with_capacity(tokens.len());
+        for (i, &token) in tokens.iter().enumerate() {
+            let next = tokens.get(i + 1);
+            match ENDS_WITH_PERIOD.captures(token) {
+                Some(caps) => {
+                    let prefix = caps.get(1).unwrap().as_str();
+                    if (prefix.contains('.') && prefix.chars().any(char::is_alphabetic))
+                        || self.nonbreaking_prefixes.contains(prefix)
+                        || next
+                            .and_then(|next| next.chars().next())
+                            .is_some_and(char::is_lowercase)
+                        || (self.numeric_only_prefixes.contains(prefix)
+                            && next.is_some_and(|next| STARTS_WITH_DIGITS.is_match(next)))
+                    {
+                        result.push(token.to_string());
+                    } else {
+                        result.push(format!("{} .", prefix));
+                    }
+                }
+                None => result.push(token.to_string()),
+            }
+        }
+        result.join(" ")
+    }
+}
+
+fn replace_multidots(text: &str) -> String {
+    let mut text = replace(text, &REPLACE_DOT_WITH_LITERALSTRING_1);
+    while text.contains("DOTMULTI.") {
+        text = replace(&text, &REPLACE_DOT_WITH_LITERALSTRING_2);
+        text = replace(&text, &REPLACE_DOT_WITH_LITERALSTRING_3);
+    }
+    text
+}
+
+fn restore_multidots(text: &str) -> String {
+    let mut text = text.to_string();
+    while text.contains("DOTDOTMULTI") {
+        text = text.replace("DOTDOTMULTI", "DOTMULTI.");
+    }
+    text.replace("DOTMULTI", ".")
+}
+
+/// The Moses detokenizer, joining tokens back into a line of text as
+/// ``detokenizer.perl`` does.
+///
+/// Punctuation is attached to the token on its left or right, quotes are paired up,
+/// and English contractions, French and Italian elisions, Czech decimal numbers
+/// and Finnish case suffixes are rejoined. The `@-@` tokens of the aggressive dash
+/// splits are joined back into hyphens.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::moses::MosesDetokenizer;
+/// let detokenizer = MosesDetokenizer::new("en");
+/// let text = detokenizer.detokenize(&["&quot;", "It", "&apos;s", "self", "@-@", "driving", ",", "\"", "he", "said", "."]);
+/// # assert_eq!(text, "\"It's self-driving,\" he said.");
+/// let detokenizer = MosesDetokenizer::new("fr");
+/// let text = detokenizer.detokenize(&["L'", "amour", "?"]);
+/// # assert_eq!(text, "L'amour ?");
+/// ```
+#[derive(Debug)]
+pub struct MosesDetokenizer {
+    lang: String,
+    unescape: bool,
+}
+
+impl Default for MosesDetokenizer {
+    fn default() -> Self {
+        Self::new("en")
+    }
+}
+
+impl MosesDetokenizer {
+    /// Initializes an instance of the [MosesDetokenizer] struct for the language
+    /// *lang*, given by its ISO 639-1 code ("en", "fr", ...).
+    pub fn new(lang: &str) -> Self {
+        Self {
+            lang: lang.to_string(),
+            unescape: true,
+        }
+    }
+
+    /// Unescapes the XML special characters of the tokens (the default), see [xml_unescape].
+    pub fn set_unescape(mut self, yes: bool) -> Self {
+        self.unescape = yes;
+        self
+    }
+
+    /// Returns the language of the detokenizer
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    /// Return the text of the *tokens*.
+    pub fn detokenize<S: AsRef<str>>(&self, tokens: &[S]) -> String {
+        // Convert the list of tokens into a string and pad it with spaces.
+        let text = format!(
+            " {} ",
+            tokens
+                .iter()
+                .map(AsRef::as_ref)
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        // Detokenize the agressive hyphen split.
+        let mut text = replace(&text, &AGGRESSIVE_HYPHEN_JOIN);
+        if self.unescape {
+            // Unescape the XML symbols.
+            text = xml_unescape(&text);
+        }
+        let lang = self.lang.as_str();
+
+        // Keep track of no. of quotation marks.
+        let mut quote_counts: HashMap<&str, usize> = HashMap::new();
+        // Controls the "effects" of detokenization, whether a space goes
+        // before the next token.
+        let mut prepend_space = " ";
+        let mut detokenized_text = String::new();
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let mut i = 0;
+        while i < tokens.len() {
+            let token = tokens[i];
+            let first = token.chars().next().unwrap();
+            if is_cjk(first) && lang != "ko" {
+                // Perform left shift if this is a second consecutive CJK word.
+                if i > 0 && tokens[i - 1].chars().last().is_some_and(is_cjk) {
+                    detokenized_text.push_str(token);
+                } else {
+                    // But do nothing special if this is a CJK word that doesn't follow a CJK word
+                    detokenized_text.push_str(prepend_space);
+                    detokenized_text.push_str(token);
+                }
+                prepend_space = " ";
+            } else if RIGHT_SHIFT_PUNCT.is_match(token) {
+                // Perform right shift on currency and other random punctuation items
+                detokenized_text.push_str(prepend_space);
+                detokenized_text.push_str(token);
+                prepend_space = "";
+            } else if LEFT_SHIFT_PUNCT.is_match(token) {
+                // In French, these punctuations are prefixed with a non-breakable space.
+                if lang == "fr" && FRENCH_SPACED_PUNCT.is_match(token) {
+                    detokenized_text.push(' ');
+                }
+                // Perform left shift on punctuation items.
+                detokenized_text.push_str(token);
+                prepend_space = " ";
+            } else if lang == "en" && i > 0 && ENGLISH_CONTRACTION.is_match(token) {
+                // For English, left-shift the contraction.
+                detokenized_text.push_str(token);
+                prepend_space = " ";
+            } else if lang == "cs"
+                && i > 1
+                && NUMBER.is_match(tokens[i - 2])
+                && DECIMAL_POINT.is_match(tokens[i - 1])
+                && NUMBER.is_match(token)
+            {
+                // In Czech, left-shift floats that are decimal numbers.
+                detokenized_text.push_str(token);
+                prepend_space = " ";
+            } else if matches!(lang, "fr" | "it" | "ga")
+                && i + 1 < tokens.len()
+                && ELISION.is_match(token)
+                && ALPHA_START.is_match(tokens[i + 1])
+            {
+                // For French and Italian, right-shift the contraction.
+                detokenized_text.push_str(prepend_space);
+                detokenized_text.push_str(token);
+                prepend_space = "";
+            } else if lang == "cs"
+                && i + 2 < tokens.len()
+                && ALPHA_END.is_match(token)
+                && CZECH_DASH.is_match(tokens[i + 1])
+                && CZECH_DASHED_WORD.is_match(tokens[i + 2])
+            {
+                // In Czech, right-shift "-li" and a few Czech dashed words (e.g. e-mail)
+                detokenized_text.push_str(prepend_space);
+                detokenized_text.push_str(token);
+                detokenized_text.push_str(tokens[i + 1]);
+                // Advance over the dash
+                i += 1;
+                prepend_space = "";
+            } else if QUOTES.is_match(token) {
+                // Combine punctuation smartly.
+                let normalized_quo = if DOUBLE_QUOTES.is_match(token) {
+                    "\""
+                } else {
+                    token
+                };
+                let count = quote_counts.entry(normalized_quo).or_insert(0);
+                if lang == "cs" && token == "„" {
+                    *count = 0;
+                }
+                if lang == "cs" && token == "“" {
+                    *count = 1;
+                }
+
+                if count.is_multiple_of(2) {
+                    if lang == "en" && token == "'" && i > 0 && tokens[i - 1].ends_with('s') {
+                        // Left shift on single quote for possessives ending
+                        // in "s", e.g. "The Jones' house"
+                        detokenized_text.push_str(token);
+                        prepend_space = " ";
+                    } else {
+                        // Right shift.
+                        detokenized_text.push_str(prepend_space);
+                        detokenized_text.push_str(token);
+                        prepend_space = "";
+                        *count += 1;
+                    }
+                } else {
+                    // Left shift.
+                    detokenized_text.push_str(token);
+                    prepend_space = " ";
+                    *count += 1;
+                }
+            } else if lang == "fi"
+                && i > 0
+                && tokens[i - 1].ends_with(':')
+                && FINNISH_CASE_SUFFIX.is_match(token)
+            {
+                // Finnish : without intervening space if followed by case suffix
+                // EU:N EU:n EU:ssa EU:sta EU:seen EU:iin EU:ista EU:ita EU:lle EU:lta EU:ksi EU:lla EU:n EU:na EU:nen EU:ta EU:ssä EU:stä EU:hen EU:ltä EU:llä EU:nä EU:tä
+                detokenized_text.push_str(token);
+                prepend_space = " ";
+            } else {
+                detokenized_text.push_str(prepend_space);
+                detokenized_text.push_str(token);
+                prepend_space = " ";
+            }
+            i += 1;
+        }
+
+        // Merge multiple spaces.
+        let detokenized_text = replace(&detokenized_text, &ONE_SPACE);
+        // Removes heading and trailing spaces.
+        detokenized_text.trim().to_string()
+    }
+}
+
+#[test]
+fn test_moses_tokenize() {
+    let tokenizer = MosesTokenizer::default();
+    let text = "This ain't funny. It's actually hillarious, yet double Ls. | [] < > [ ] & You're gonna shake it off? Don't?";
+    let expected = vec![
+        "This",
+        "ain",
+        "&apos;t",
+        "funny",
+        ".",
+        "It",
+        "&apos;s",
+        "actually",
+        "hillarious",
+        ",",
+        "yet",
+        "double",
+        "Ls",
+        ".",
+        "&#124;",
+        "&#91;",
+        "&#93;",
+        "&lt;",
+        "&gt;",
+        "&#91;",
+        "&#93;",
+        "&amp;",
+        "You",
+        "&apos;re",
+        "gonna",
+        "shake",
+        "it",
+        "off",
+        "?",
+        "Don",
+        "&apos;t",
+        "?",
+    ];
+    assert_eq!(tokenizer.tokenize(text), expected);
+
+    let detokenizer = MosesDetokenizer::default();
+    assert_eq!(
+        detokenizer.detokenize(&expected),
+        "This ain't funny. It's actually hillarious, yet double Ls. | [] < > [] & You're gonna shake it off? Don't?"
+    );
+
+    assert!(matches!(
+        MosesTokenizer::new("de"),
+        Err(TokenizeError::UnsupportedLanguage(_))
+    ));
+    let tokenizer = MosesTokenizer::with_nonbreaking_prefixes(
+        "de",
+        "Nr #NUMERIC_ONLY#\n# comment\nz.B\n".as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(
+        tokenizer.tokenize("Ruf Nr. 5 an. Mr. X"),
+        vec!["Ruf", "Nr.", "5", "an", ".", "Mr", ".", "X"]
+    );
+    assert!(tokenizer.tokenize(" \t ").is_empty());
+    assert_eq!(
+        MosesDetokenizer::new("zh").detokenize(&["我", "爱", "你", "ok"]),
+        "我爱你 ok"
+    );
+    let detokenizer = MosesDetokenizer::new("cs");
+    assert_eq!(
+        detokenizer.detokenize(&["Pošli", "mi", "e", "-", "mail", "."]),
+        "Pošli mi e-mail."
+    );
+    assert_eq!(
+        detokenizer.detokenize(&["Chceš", "-", "li", "jít", "."]),
+        "Chceš-li jít."
+    );
+}
@@ -0,0 +1,185 @@
+//! Multi-Word Expression Tokenizer
+//!
+//! A ``MWETokenizer`` takes a string which has already been divided into tokens and
+//! retokenizes it, merging multi-word expressions into single tokens, using a lexicon
+//! of MWEs:
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::mwe::MWETokenizer;
+//! let mut tokenizer = MWETokenizer::new(&[&["a", "little"], &["a", "little", "bit"], &["a", "lot"]], None);
+//! tokenizer.add_mwe(&["in", "spite", "of"]);
+//! let s = "In a little or a little bit or a lot in spite of";
+//! let result = tokenizer.tokenize(&s.split(' ').collect::<Vec<_>>());
+//! # assert_eq!(result, vec!["In", "a_little", "or", "a_little_bit", "or", "a_lot", "in_spite_of"]);
+//! ```
+
+use std::ops::Range;
+
+use crate::collections::Trie;
+
+use super::util::{Token, TokenizeError};
+
+/// A tokenizer that processes tokenized text and merges multi-word expressions
+/// into single tokens.
+///
+/// The expressions are matched greedily, the longest expression starting at a token wins.
+#[derive(Debug)]
+pub struct MWETokenizer {
+    mwes: Trie<String>,
+    separator: String,
+}
+impl Default for MWETokenizer {
+    fn default() -> Self {
+        Self {
+            mwes: Trie::new(),
+            separator: "_".to_string(),
+        }
+    }
+}
+impl MWETokenizer {
+    /// Initializes an instance of the [MWETokenizer] struct
+    /// mwes: A sequence of multi-word expressions to be merged, where
+    ///     each MWE is a sequence of strings.
+    /// separator: String that should be inserted between words in a multi-word
+    ///     expression token, defaults to "_"
+    pub fn new<'s, P>(mwes: &[&[&str]], separator: P) -> Self
+    where
+        P: Into<Option<&'s str>>,
+    {
+        Self {
+            mwes: mwes
+                .iter()
+                .map(|mwe| mwe.iter().map(|word| word.to_string()))
+                .collect(),
+            separator: separator.into().unwrap_or("_").to_string(),
+        }
+    }
+
+    /// Add a multi-word expression to the lexicon (stored as a word trie)
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::mwe::MWETokenizer;
+    /// let mut tokenizer = MWETokenizer::default();
+    /// tokenizer.add_mwe(&["a", "b"]);
+    /// tokenizer.add_mwe(&["a", "b", "c"]);
+    /// tokenizer.add_mwe(&["a", "x"]);
+    /// let result = tokenizer.tokenize(&["a", "b", "c", "a", "x", "a"]);
+    /// # assert_eq!(result, vec!["a_b_c", "a_x", "a"]);
+    /// ```
+    pub fn add_mwe(&mut self, mwe: &[&str]) {
+        self.mwes.insert(mwe.iter().map(|word| word.to_string()));
+    }
+
+    /// Returns the separator inserted between the words of a merged expression
+    pub fn separator(&self) -> &str {
+        &self.separator
+    }
+
+    /// Return *text* with the multi-word expressions merged into single tokens.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::mwe::MWETokenizer;
+    /// let tokenizer = MWETokenizer::new(&[&["hors", "d'oeuvre"]], "+");
+    /// let result = tokenizer.tokenize(&["An", "hors", "d'oeuvre", "tonight,", "sir?"]);
+    /// # assert_eq!(result, vec!["An", "hors+d'oeuvre", "tonight,", "sir?"]);
+    /// ```
+    pub fn tokenize<S: AsRef<str>>(&self, text: &[S]) -> Vec<String> {
+        self.merge(text)
+            .into_iter()
+            .map(|group| {
+                text[group]
+                    .iter()
+                    .map(AsRef::as_ref)
+                    .collect::<Vec<_>>()
+                    .join(&self.separator)
+            })
+            .collect()
+    }
+
+    /// Return the spans of the tokens returned by [MWETokenizer::tokenize],
+    /// given the tokenized *text* and the *spans* of its tokens.
+    /// A merged token covers the union of the spans of its words.
+    ///
+    /// Returns [TokenizeError::MismatchError] if there isn't one span per token.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::mwe::MWETokenizer;
+    /// // "I moved to  New York"
+    /// let words = ["I", "moved", "to", "New", "York"];
+    /// let spans = [(0, 1), (2, 7), (8, 10), (12, 15), (16, 20)];
+    /// let tokenizer = MWETokenizer::new(&[&["New", "York"]], None);
+    /// let result = tokenizer.span_tokenize(&words, &spans).unwrap();
+    /// # assert_eq!(result, vec![(0, 1), (2, 7), (8, 10), (12, 20)]);
+    /// ```
+    pub fn span_tokenize<S: AsRef<str>>(
+        &self,
+        text: &[S],
+        spans: &[Token],
+    ) -> Result<Vec<Token>, TokenizeError> {
+        if text.len() != spans.len() {
+            return Err(TokenizeError::MismatchError);
+        }
+        Ok(self
+            .merge(text)
+            .into_iter()
+            .map(|group| {
+                spans[group]
+                    .iter()
+                    .fold((usize::MAX, 0), |(start, end), span| {
+                        (start.min(span.0), end.max(span.1))
+                    })
+            })
+            .collect())
+    }
+
+    // Groups the indices of the tokens making up each output token
+    fn merge<S: AsRef<str>>(&self, text: &[S]) -> Vec<Range<usize>> {
+        let mut result = Vec::new();
+        let mut i = 0;
+        while i < text.len() {
+            // possible MWE match, keeping the longest one
+            let mut trie = &self.mwes;
+            let mut last_match = None;
+            for (j, word) in text[i..].iter().enumerate() {
+                match trie.get(word.as_ref()) {
+                    Some(child) => trie = child,
+                    None => break,
+                }
+                if trie.is_leaf() {
+                    last_match = Some(i + j + 1);
+                }
+            }
+            // no match, so backtrack
+            let end = last_match.unwrap_or(i + 1);
+            result.push(i..end);
+            i = end;
+        }
+        result
+    }
+}
+
+#[test]
+fn test_mwe_tokenize() {
+    let tokenizer = MWETokenizer::new(&[&["a", "little"], &["a", "little", "bit"]], None);
+    let tokens = ["This", "is", "a", "test", "in", "spite", "a", "little"];
+    assert_eq!(
+        tokenizer.tokenize(&tokens),
+        vec!["This", "is", "a", "test", "in", "spite", "a_little"]
+    );
+    let tokens = ["a", "little", "rest"];
+    assert_eq!(tokenizer.tokenize(&tokens), vec!["a_little", "rest"]);
+    assert!(matches!(
+        tokenizer.span_tokenize(&tokens, &[(0, 1)]),
+        Err(TokenizeError::MismatchError)
+    ));
+}
@@ -0,0 +1,188 @@
+//! Tokenizer Pipelines
+//!
+//! A ``Pipeline`` chains tokenizers, each one tokenizing the tokens of the one before,
+//! e.g. paragraphs, then sentences, then words. The spans found at every stage are
+//! byte offsets into the original text, and the tokens keep their nesting:
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::destructive::NLTKWordTokenizer;
+//! # use lib_nltk::tokenize::pipeline::Pipeline;
+//! # use lib_nltk::tokenize::punkt::PunktSentenceTokenizer;
+//! # use lib_nltk::tokenize::regexp::BlanklineTokenizer;
+//! let text = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\n\nThanks.";
+//! let pipeline = Pipeline::new()
+//!     .then(BlanklineTokenizer())
+//!     .then(PunktSentenceTokenizer::english())
+//!     .then(NLTKWordTokenizer::new());
+//! let result: Vec<_> = pipeline
+//!     .leaves(text)
+//!     .into_iter()
+//!     .map(|(path, (start, end))| (path, &text[start..end]))
+//!     .collect();
+//! // "two", the fourth word of the second sentence of the first paragraph
+//! assert_eq!(result[12], (vec![0, 1, 3], "two"));
+//! # assert_eq!(result[16], (vec![1, 0, 0], "Thanks"));
+//! ```
+
+use super::api::TokenizerI;
+use super::registry::BoxedTokenizer;
+use super::util::Token;
+
+/// A token found by a stage of a [Pipeline], along with the tokens the next stages
+/// found within it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Segment {
+    /// The byte offsets of the token in the original text
+    pub span: Token,
+    /// The tokens of the next stage within the token, empty for the last stage
+    pub children: Vec<Segment>,
+}
+
+impl Segment {
+    /// Returns the text of the segment, given the original *text*
+    pub fn text<'a>(&self, text: &'a str) -> &'a str {
+        &text[self.span.0..self.span.1]
+    }
+}
+
+/// A chain of tokenizers, each stage tokenizing the tokens of the stage before.
+///
+/// A pipeline is a tokenizer too: its tokens are the tokens of its last stage, and a
+/// pipeline without stages returns the whole text as a single token.
+#[derive(Default)]
+pub struct Pipeline {
+    stages: Vec<BoxedTokenizer>,
+}
+
+impl Pipeline {
+    /// Initializes a [Pipeline] without stages
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds *tokenizer* as the last stage of the pipeline
+    pub fn then<T>(mut self, tokenizer: T) -> Self
+    where
+        T: TokenizerI + Send + Sync + 'static,
+    {
+        self.stages.push(Box::new(tokenizer));
+        self
+    }
+
+    /// Returns the number of stages
+    pub fn len(&self) -> usize {
+        self.stages.len()
+    }
+
+    /// Returns if there are no stages
+    pub fn is_empty(&self) -> bool {
+        self.stages.is_empty()
+    }
+
+    /// Returns the tokens of the first stage found in *text*, each with the tokens
+    /// of the following stages nested in it.
+    pub fn segments(&self, text: &str) -> Vec<Segment> {
+        self.segment(text, (0, text.len()), &self.stages)
+    }
+
+    /// Returns the tokens of the last stage found in *text*, each with its path: its index
+    /// among the tokens of its parent, for the token of every stage it is nested in.
+    ///
+    /// The path of a word of a (paragraph, sentence, word) pipeline is
+    /// ``[paragraph, sentence within the paragraph, word within the sentence]``.
+    pub fn leaves(&self, text: &str) -> Vec<(Vec<usize>, Token)> {
+        let mut result = Vec::new();
+        let mut path = Vec::new();
+        collect_leaves(&self.segments(text), self.len(), &mut path, &mut result);
+        result
+    }
+
+    fn segment(&self, text: &str, span: Token, stages: &[BoxedTokenizer]) -> Vec<Segment> {
+        let (start, end) = span;
+        match stages.split_first() {
+            Some((stage, rest)) => stage
+                .span_tokenize(&text[start..end])
+                .into_iter()
+                .map(|(s, e)| {
+                    let span = (start + s, start + e);
+                    Segment {
+                        span,
+                        children: self.segment(text, span, rest),
+                    }
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+// Collects the segments of the last stage, at the given depth, with their paths
+fn collect_leaves(
+    segments: &[Segment],
+    depth: usize,
+    path: &mut Vec<usize>,
+    result: &mut Vec<(Vec<usize>, Token)>,
+) {
+    for (i, segment) in segments.iter().enumerate() {
+        path.push(i);
+        if path.len() == depth {
+            result.push((path.clone(), segment.span));
+        } else {
+            collect_leaves(&segment.children, depth, path, result);
+        }
+        path.pop();
+    }
+}
+
+impl TokenizerI for Pipeline {
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
+    }
+
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        if self.is_empty() {
+            return vec![(0, sent.len())];
+        }
+        self.leaves(sent)
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect()
+    }
+}
+
+#[test]
+fn test_pipeline() {
+    use super::bpe::BPETokenizer;
+    use super::regexp::{BlanklineTokenizer, WhitespaceTokenizer};
+    use super::simple::SpaceTokenizer;
+    use crate::probability::FreqDist;
+
+    let text = "low lower\n\nnewest  lowest";
+    let mut words: FreqDist<&str> = FreqDist::default();
+    words.init(text.split_whitespace());
+    let pipeline = Pipeline::new()
+        .then(BlanklineTokenizer())
+        .then(WhitespaceTokenizer())
+        .then(BPETokenizer::train(&words, 5));
+
+    let segments = pipeline.segments(text);
+    assert_eq!(segments.len(), 2);
+    assert_eq!(segments[1].text(text), "newest  lowest");
+    assert_eq!(segments[1].children[1].text(text), "lowest");
+    for (path, span) in pipeline.leaves(text) {
+        let word = &segments[path[0]].children[path[1]];
+        assert!(word.span.0 <= span.0 && span.1 <= word.span.1);
+    }
+    assert_eq!(pipeline.tokenize(text).concat(), "lowlowernewestlowest");
+
+    // a token without tokens in the next stage doesn't make it to the last one
+    let pipeline = Pipeline::new()
+        .then(SpaceTokenizer)
+        .then(WhitespaceTokenizer());
+    assert_eq!(pipeline.span_tokenize("a  b"), vec![(0, 1), (3, 4)]);
+    assert_eq!(Pipeline::new().tokenize("a b"), vec!["a b"]);
+}
@@ -0,0 +1,1540 @@
+//! Punkt Sentence Tokenizer
+//!
+//! This tokenizer divides a text into a list of sentences
+//! by using an unsupervised algorithm to build a model for abbreviation
+//! words, collocations, and words that start sentences.  It must be
+//! trained on a large collection of plaintext in the target language
+//! before it can be used.
+//!
+//! The NLTK data package includes a pre-trained Punkt tokenizer for
+//! English, here parameters for English trained with [PunktTrainer]
+//! are built in, see [PunktParameters::english].
+//!
+//! Punkt is designed to learn parameters (a list of abbreviations, etc.)
+//! unsupervised from a corpus similar to the target domain. The pre-packaged
+//! models may therefore be unsuitable: use [PunktTrainer] to learn parameters
+//! from the given text.
+//!
+//! [PunktTrainer] learns parameters such as a list of abbreviations
+//! (without supervision) from portions of text. Using a [PunktTrainer]
+//! directly allows for incremental training and modification of the
+//! hyper-parameters used to decide what is considered an abbreviation, etc.
+//!
+//! The algorithm for this tokenizer is described in:
+//!
+//! Kiss, Tibor and Strunk, Jan (2006): Unsupervised Multilingual Sentence
+//!   Boundary Detection.  Computational Linguistics 32: 485-525.
+//!
+//! Copyright (C) 2001-2022 NLTK Project
+//! Algorithm: Kiss & Strunk (2006)
+//! Author: Willy <willy@csse.unimelb.edu.au> (original Python port)
+//!         Steven Bird <stevenbird1@gmail.com> (additions)
+//!         Edward Loper <edloper@gmail.com> (rewrite)
+//!         Joel Nothman <jnothman@student.usyd.edu.au> (almost rewrite)
+//!         Arthur Darcet <arthur@darcet.fr> (fixes)
+//!         Tom Aarsen <> (tackle ReDoS & performance issues)
+//! URL: <https://www.nltk.org/>
+//! For license information, see LICENSE.TXT
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Write};
+
+use counter::Counter;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::api::TokenizerI;
+use super::util::Token;
+
+//****************************************************************************************************************************
+//  Orthographic Context Constants
+//****************************************************************************************************************************
+
+// The following constants are used to describe the orthographic
+// contexts in which a word can occur.  BEG=beginning, MID=middle,
+// UNK=unknown, UC=uppercase, LC=lowercase, NC=no case.
+
+/// Orthographic context: beginning of a sentence with upper case.
+const ORTHO_BEG_UC: u32 = 1 << 1;
+/// Orthographic context: middle of a sentence with upper case.
+const ORTHO_MID_UC: u32 = 1 << 2;
+/// Orthographic context: unknown position in a sentence with upper case.
+const ORTHO_UNK_UC: u32 = 1 << 3;
+/// Orthographic context: beginning of a sentence with lower case.
+const ORTHO_BEG_LC: u32 = 1 << 4;
+/// Orthographic context: middle of a sentence with lower case.
+const ORTHO_MID_LC: u32 = 1 << 5;
+/// Orthographic context: unknown position in a sentence with lower case.
+const ORTHO_UNK_LC: u32 = 1 << 6;
+/// Orthographic context: occurs with upper case.
+const ORTHO_UC: u32 = ORTHO_BEG_UC + ORTHO_MID_UC + ORTHO_UNK_UC;
+/// Orthographic context: occurs with lower case.
+const ORTHO_LC: u32 = ORTHO_BEG_LC + ORTHO_MID_LC + ORTHO_UNK_LC;
+
+//****************************************************************************************************************************
+//  Language-dependent variables
+//****************************************************************************************************************************
+
+// Characters which are candidates for sentence boundaries
+const SENT_END_CHARS: [char; 3] = ['.', '?', '!'];
+// Sentence internal punctuation, which indicates an abbreviation if
+// preceded by a period-final token.
+const INTERNAL_PUNCTUATION: [char; 3] = [',', ':', ';'];
+// Tokens which are always regarded as punctuation by the orthographic heuristic
+const PUNCTUATION: [&str; 6] = [";", ":", ",", ".", "!", "?"];
+
+// Excludes some characters from starting word tokens
+fn is_word_start(c: char) -> bool {
+    !c.is_whitespace() && !"(\"`{[:;&#*@)}]-,".contains(c)
+}
+
+// Characters that cannot appear within words
+fn is_non_word_char(c: char) -> bool {
+    ")\";}]*:@'({[!?".contains(c)
+}
+
+// Hyphen and ellipsis are multi-character punctuation: `(?:\-{2,}|\.{2,}|(?:\.\s){2,}\.)`
+// Returns the byte length of the punctuation found at the start of `text`.
+fn multi_char_punct(text: &str) -> Option<usize> {
+    for repeated in ['-', '.'] {
+        let count = text.chars().take_while(|&c| c == repeated).count();
+        if count >= 2 {
+            return Some(count);
+        }
+    }
+
+    // Spaced out ellipsis, ". . ."
+    let mut ends = vec![];
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, '.')) = chars.next() {
+        match chars.next() {
+            Some((i, c)) if c.is_whitespace() => ends.push(i + c.len_utf8()),
+            _ => break,
+        }
+    }
+    // Backtrack until the pairs of ". " are followed by a final period
+    while ends.len() >= 2 {
+        let end = *ends.last().unwrap();
+        if text[end..].starts_with('.') {
+            return Some(end + 1);
+        }
+        ends.pop();
+    }
+    None
+}
+
+// Returns the byte length of the word starting at the beginning of `text`,
+// a word accepts characters until a sequence marking the word's end is found.
+fn word_length(text: &str) -> usize {
+    let mut chars = text.char_indices().skip(1).peekable();
+    while let Some(&(i, c)) = chars.peek() {
+        let rest = &text[i..];
+        let ends_word = c.is_whitespace()
+            || is_non_word_char(c)
+            || multi_char_punct(rest).is_some()
+            // Comma if at end of word
+            || (c == ',' && {
+                let after = &rest[1..];
+                match after.chars().next() {
+                    None => true,
+                    Some(n) => n.is_whitespace() || is_non_word_char(n) || multi_char_punct(after).is_some(),
+                }
+            });
+        if ends_word {
+            return i;
+        }
+        chars.next();
+    }
+    text.len()
+}
+
+/// Tokenize a string to split off punctuation other than periods
+fn word_tokenize(text: &str) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut index = 0;
+    while let Some(c) = text[index..].chars().next() {
+        if c.is_whitespace() {
+            index += c.len_utf8();
+            continue;
+        }
+        let rest = &text[index..];
+        let len = match multi_char_punct(rest) {
+            Some(len) => len,
+            None if is_word_start(c) => word_length(rest),
+            None => c.len_utf8(),
+        };
+        result.push(&rest[..len]);
+        index += len;
+    }
+    result
+}
+
+// A potential sentence ending found by `period_contexts`, all values are byte offsets.
+#[derive(Debug)]
+struct PeriodContext {
+    // Start of the sentence ending character
+    start: usize,
+    // End of the sentence ending character
+    end: usize,
+    // End of either the following punctuation, or the following token
+    after_end: usize,
+    // Start of the following token, if it is separated by whitespace
+    next_tok: Option<usize>,
+}
+
+/// Finds the potential sentence endings, those followed by either other
+/// punctuation, or by whitespace and some other token.
+fn period_contexts(text: &str) -> Vec<PeriodContext> {
+    let mut result = Vec::new();
+    for (start, c) in text.char_indices() {
+        if !SENT_END_CHARS.contains(&c) {
+            continue;
+        }
+        let end = start + c.len_utf8();
+        let rest = &text[end..];
+        match rest.chars().next() {
+            Some(n) if is_non_word_char(n) => result.push(PeriodContext {
+                start,
+                end,
+                after_end: end + n.len_utf8(),
+                next_tok: None,
+            }),
+            Some(n) if n.is_whitespace() => {
+                let next_tok = end + (rest.len() - rest.trim_start().len());
+                let token_length = text[next_tok..]
+                    .find(char::is_whitespace)
+                    .unwrap_or(text.len() - next_tok);
+                if token_length > 0 {
+                    result.push(PeriodContext {
+                        start,
+                        end,
+                        after_end: next_tok + token_length,
+                        next_tok: Some(next_tok),
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+// Returns the byte length of the closing punctuation at the start of `text`
+// which belongs to the previous sentence: `["\')\]}]+?(?:\s+|(?=--)|$)`.
+fn boundary_realignment(text: &str) -> Option<usize> {
+    let mut index = 0;
+    while let Some(c) = text[index..].chars().next() {
+        if !"\"')]}".contains(c) {
+            break;
+        }
+        index += c.len_utf8();
+        let rest = &text[index..];
+        match rest.chars().next() {
+            None => return Some(index),
+            Some(n) if n.is_whitespace() => {
+                return Some(index + (rest.len() - rest.trim_start().len()))
+            }
+            Some(_) if rest.starts_with("--") => return Some(index),
+            _ => {}
+        }
+    }
+    None
+}
+
+//****************************************************************************************************************************
+//  Punkt Token
+//****************************************************************************************************************************
+
+lazy_static! {
+    static ref RE_ELLIPSIS: Regex = Regex::new(r"^\.\.+$").unwrap();
+    static ref RE_NUMERIC: Regex = Regex::new(r"^-?[\.,]?\d[\d,\.-]*\.?$").unwrap();
+    static ref RE_INITIAL: Regex = Regex::new(r"^[^\W\d]\.$").unwrap();
+    static ref RE_ALPHA: Regex = Regex::new(r"^[^\W\d]+$").unwrap();
+    static ref RE_NON_PUNCT: Regex = Regex::new(r"[^\W\d]").unwrap();
+}
+
+/// Stores a token of text with annotations produced during
+/// sentence boundary detection.
+#[derive(Debug, Clone)]
+struct PunktToken<'a> {
+    tok: &'a str,
+    typ: String,
+    period_final: bool,
+    parastart: bool,
+    linestart: bool,
+    sentbreak: bool,
+    abbr: bool,
+    ellipsis: bool,
+}
+impl<'a> PunktToken<'a> {
+    fn new(tok: &'a str) -> Self {
+        Self {
+            tok,
+            // Returns a case-normalized representation of the token.
+            typ: RE_NUMERIC
+                .replace(&tok.to_lowercase(), "##number##")
+                .into_owned(),
+            period_final: tok.ends_with('.'),
+            parastart: false,
+            linestart: false,
+            sentbreak: false,
+            abbr: false,
+            ellipsis: false,
+        }
+    }
+
+    /// The type with its final period removed if it has one.
+    fn type_no_period(&self) -> &str {
+        if self.typ.chars().count() > 1 && self.typ.ends_with('.') {
+            &self.typ[..self.typ.len() - 1]
+        } else {
+            &self.typ
+        }
+    }
+
+    /// The type with its final period removed if it is marked as a sentence break.
+    fn type_no_sentperiod(&self) -> &str {
+        if self.sentbreak {
+            self.type_no_period()
+        } else {
+            &self.typ
+        }
+    }
+
+    /// True if the token's first character is uppercase.
+    fn first_upper(&self) -> bool {
+        self.tok.chars().next().is_some_and(char::is_uppercase)
+    }
+
+    /// True if the token's first character is lowercase.
+    fn first_lower(&self) -> bool {
+        self.tok.chars().next().is_some_and(char::is_lowercase)
+    }
+
+    /// True if the token text is that of an ellipsis.
+    fn is_ellipsis(&self) -> bool {
+        RE_ELLIPSIS.is_match(self.tok)
+    }
+
+    /// True if the token text is that of a number.
+    fn is_number(&self) -> bool {
+        self.typ.starts_with("##number##")
+    }
+
+    /// True if the token text is that of an initial.
+    fn is_initial(&self) -> bool {
+        RE_INITIAL.is_match(self.tok)
+    }
+
+    /// True if the token text is all alphabetic.
+    fn is_alpha(&self) -> bool {
+        RE_ALPHA.is_match(self.tok)
+    }
+
+    /// True if the token is either a number or is alphabetic.
+    fn is_non_punct(&self) -> bool {
+        RE_NON_PUNCT.is_match(&self.typ)
+    }
+}
+
+/// Divide the given text into tokens, using the punkt word
+/// segmentation regular expression, and generate the resulting list
+/// of tokens augmented as three-tuples with two boolean values for whether
+/// the given token occurs at the start of a paragraph or a new line,
+/// respectively.
+fn tokenize_words(plaintext: &str) -> Vec<PunktToken<'_>> {
+    let mut result = Vec::new();
+    let mut parastart = false;
+    for line in plaintext.split('\n') {
+        if line.trim().is_empty() {
+            parastart = true;
+            continue;
+        }
+        let mut line_toks = word_tokenize(line).into_iter();
+        if let Some(tok) = line_toks.next() {
+            let mut token = PunktToken::new(tok);
+            token.parastart = parastart;
+            token.linestart = true;
+            result.push(token);
+            parastart = false;
+        }
+        result.extend(line_toks.map(PunktToken::new));
+    }
+    result
+}
+
+/// Performs a token-based classification (section 4) over the given
+/// tokens, making use of the orthographic heuristic (4.1.1), collocation
+/// heuristic (4.1.2) and frequent sentence starter heuristic (4.1.3).
+fn annotate_first_pass(tokens: &mut [PunktToken], params: &PunktParameters) {
+    for aug_tok in tokens.iter_mut() {
+        let tok = aug_tok.tok;
+        if tok.len() == 1 && SENT_END_CHARS.iter().any(|&c| tok.starts_with(c)) {
+            aug_tok.sentbreak = true;
+        } else if aug_tok.is_ellipsis() {
+            aug_tok.ellipsis = true;
+        } else if aug_tok.period_final && !tok.ends_with("..") {
+            let without_period = tok[..tok.len() - 1].to_lowercase();
+            if params.abbrev_types.contains(&without_period)
+                || params
+                    .abbrev_types
+                    .contains(without_period.rsplit('-').next().unwrap())
+            {
+                aug_tok.abbr = true;
+            } else {
+                aug_tok.sentbreak = true;
+            }
+        }
+    }
+}
+
+//****************************************************************************************************************************
+//  Punkt Parameters
+//****************************************************************************************************************************
+
+/// Stores data used to perform sentence boundary detection with Punkt.
+///
+/// The parameters can be written out with [PunktParameters::save] and read back in with
+/// [PunktParameters::load], using a plain text format of one entry per line under
+/// ``[abbrev_types]``, ``[collocations]``, ``[sent_starters]`` and ``[ortho_context]`` headers.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::punkt::PunktParameters;
+/// let params = PunktParameters::english();
+/// let mut saved = Vec::new();
+/// params.save(&mut saved).unwrap();
+/// let loaded = PunktParameters::load(saved.as_slice()).unwrap();
+/// # assert_eq!(params, loaded);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PunktParameters {
+    // A set of word types for known abbreviations.
+    abbrev_types: HashSet<String>,
+    // A set of word type tuples for known common collocations
+    // where the first word ends in a period.  E.g., ('S.', 'Bach')
+    // is a common collocation in a text that discusses 'Johann
+    // S. Bach'.  These count as negative evidence for sentence
+    // boundaries.
+    collocations: HashSet<(String, String)>,
+    // A set of word types for words that often appear at the
+    // beginning of sentences.
+    sent_starters: HashSet<String>,
+    // A dictionary mapping word types to the set of orthographic
+    // contexts that word type appears in.
+    ortho_context: HashMap<String, u32>,
+}
+impl PunktParameters {
+    /// Returns the built in parameters for English.
+    ///
+    /// These were trained with [PunktTrainer] on general English prose: Alice's
+    /// Adventures in Wonderland, The Adventures of Sherlock Holmes and the proceedings
+    /// of the Library of Congress Workshop on Electronic Texts, from Project Gutenberg.
+    /// A few words seen only before a period, such as "tug" or "kent", were wrongly
+    /// learned as abbreviations and removed.
+    pub fn english() -> Self {
+        Self::load(ENGLISH_PARAMS.as_bytes()).unwrap()
+    }
+
+    /// Returns the word types for known abbreviations, without their final period
+    pub fn abbrev_types(&self) -> &HashSet<String> {
+        &self.abbrev_types
+    }
+
+    /// Adds a word type, without its final period, as a known abbreviation
+    pub fn add_abbrev_type(&mut self, typ: &str) {
+        self.abbrev_types.insert(typ.to_lowercase());
+    }
+
+    /// Returns the word type pairs for known collocations
+    pub fn collocations(&self) -> &HashSet<(String, String)> {
+        &self.collocations
+    }
+
+    /// Adds a pair of word types as a known collocation
+    pub fn add_collocation(&mut self, first: &str, second: &str) {
+        self.collocations
+            .insert((first.to_lowercase(), second.to_lowercase()));
+    }
+
+    /// Returns the word types that often appear at the beginning of sentences
+    pub fn sent_starters(&self) -> &HashSet<String> {
+        &self.sent_starters
+    }
+
+    /// Adds a word type as a frequent sentence starter
+    pub fn add_sent_starter(&mut self, typ: &str) {
+        self.sent_starters.insert(typ.to_lowercase());
+    }
+
+    /// Removes all the collocations
+    pub fn clear_collocations(&mut self) {
+        self.collocations.clear();
+    }
+
+    /// Removes all the sentence starters
+    pub fn clear_sent_starters(&mut self) {
+        self.sent_starters.clear();
+    }
+
+    /// Removes all the orthographic contexts
+    pub fn clear_ortho_context(&mut self) {
+        self.ortho_context.clear();
+    }
+
+    fn add_ortho_context(&mut self, typ: &str, flag: u32) {
+        *self.ortho_context.entry(typ.to_string()).or_insert(0) |= flag;
+    }
+
+    fn ortho_context(&self, typ: &str) -> u32 {
+        self.ortho_context.get(typ).copied().unwrap_or(0)
+    }
+
+    /// Writes the parameters out in the plain text format read by [PunktParameters::load].
+    ///
+    /// The entries are sorted, so the same parameters are always written out the same.
+    pub fn save<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        let mut abbrev_types: Vec<_> = self.abbrev_types.iter().collect();
+        abbrev_types.sort();
+        writeln!(writer, "[abbrev_types]")?;
+        for typ in abbrev_types {
+            writeln!(writer, "{}", typ)?;
+        }
+
+        let mut collocations: Vec<_> = self.collocations.iter().collect();
+        collocations.sort();
+        writeln!(writer, "[collocations]")?;
+        for (first, second) in collocations {
+            writeln!(writer, "{}\t{}", first, second)?;
+        }
+
+        let mut sent_starters: Vec<_> = self.sent_starters.iter().collect();
+        sent_starters.sort();
+        writeln!(writer, "[sent_starters]")?;
+        for typ in sent_starters {
+            writeln!(writer, "{}", typ)?;
+        }
+
+        let mut ortho_context: Vec<_> = self.ortho_context.iter().collect();
+        ortho_context.sort();
+        writeln!(writer, "[ortho_context]")?;
+        for (typ, flags) in ortho_context {
+            writeln!(writer, "{}\t{}", typ, flags)?;
+        }
+        Ok(())
+    }
+
+    /// Reads in the parameters written out by [PunktParameters::save].
+    ///
+    /// Returns an [io::ErrorKind::InvalidData] error for a malformed line.
+    pub fn load<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid punkt parameters line: {:?}", line),
+            )
+        };
+
+        let mut params = Self::default();
+        let mut section = String::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            // Only the exact headers start a section, a type like "[sic]" is no section
+            if matches!(
+                line.as_str(),
+                "[abbrev_types]" | "[collocations]" | "[sent_starters]" | "[ortho_context]"
+            ) {
+                section = line[1..line.len() - 1].to_string();
+                continue;
+            }
+            match section.as_str() {
+                "abbrev_types" => {
+                    params.abbrev_types.insert(line);
+                }
+                "sent_starters" => {
+                    params.sent_starters.insert(line);
+                }
+                "collocations" => {
+                    let (first, second) = line.split_once('\t').ok_or_else(|| invalid(&line))?;
+                    params
+                        .collocations
+                        .insert((first.to_string(), second.to_string()));
+                }
+                "ortho_context" => {
+                    let (typ, flags) = line.split_once('\t').ok_or_else(|| invalid(&line))?;
+                    let flags = flags.parse().map_err(|_| invalid(&line))?;
+                    params.ortho_context.insert(typ.to_string(), flags);
+                }
+                _ => return Err(invalid(&line)),
+            }
+        }
+        Ok(params)
+    }
+}
+
+// The parameters written out by PunktParameters::save after the training
+const ENGLISH_PARAMS: &str = include_str!("data/punkt_english.txt");
+
+//****************************************************************************************************************************
+//  Punkt Trainer
+//****************************************************************************************************************************
+
+/// Learns parameters used in Punkt sentence boundary detection.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::punkt::PunktTrainer;
+/// let text = "Dr. Watson met the patient at noon. The patient was ill. ".repeat(20);
+/// let mut trainer = PunktTrainer::new();
+/// trainer.train(&text, true);
+/// # assert!(trainer.get_params().abbrev_types().contains("dr"));
+/// ```
+#[derive(Debug, Default)]
+pub struct PunktTrainer {
+    // The parameters being learned
+    params: PunktParameters,
+    // A frequency distribution giving the frequency of each
+    // case-normalized token type in the training data.
+    type_fdist: Counter<String>,
+    // The number of words ending in period in the training data.
+    num_period_toks: usize,
+    // A frequency distribution giving the frequency of all
+    // bigrams in the training data where the first word ends in a
+    // period.
+    collocation_fdist: Counter<(String, String)>,
+    // A frequency distribution giving the frequency of all words
+    // that occur at the training data at the beginning of a sentence
+    // (after the first pass of annotation).
+    sent_starter_fdist: Counter<String>,
+    // The total number of sentence breaks identified in training, used for
+    // calculating the frequent sentence starter heuristic.
+    sentbreak_count: usize,
+    // A flag as to whether the training has been finalized by finding
+    // collocations and sentence starters, or whether finalize_training()
+    // still needs to be called.
+    finalized: bool,
+    include_all_collocs: bool,
+    include_abbrev_collocs: bool,
+}
+impl PunktTrainer {
+    /// cut-off value whether a 'token' is an abbreviation
+    pub const ABBREV: f64 = 0.3;
+    /// upper cut-off for Mikheev's(2002) abbreviation detection algorithm
+    pub const ABBREV_BACKOFF: usize = 5;
+    /// minimal log-likelihood value that two tokens need to be considered
+    /// as a collocation
+    pub const COLLOCATION: f64 = 7.88;
+    /// minimal log-likelihood value that a token requires to be considered
+    /// as a frequent sentence starter
+    pub const SENT_STARTER: f64 = 30.0;
+    /// this sets a minimum bound on the number of times a bigram needs to
+    /// appear before it can be considered a collocation, in addition to log
+    /// likelihood statistics. This is useful when INCLUDE_ALL_COLLOCS is True.
+    pub const MIN_COLLOC_FREQ: usize = 1;
+
+    /// Initialize a [PunktTrainer] with no learned parameters
+    pub fn new() -> Self {
+        Self {
+            finalized: true,
+            ..Self::default()
+        }
+    }
+
+    /// Initialize a [PunktTrainer] that continues training on top of existing parameters
+    pub fn with_params(params: PunktParameters) -> Self {
+        Self {
+            params,
+            ..Self::new()
+        }
+    }
+
+    /// Sets if all word pairs are considered as collocations,
+    /// rather than only those where the first word is a number or initial.
+    ///
+    /// Defaults to 'FALSE'
+    pub fn set_include_all_collocs(mut self, yes: bool) -> Self {
+        self.include_all_collocs = yes;
+        self
+    }
+
+    /// Sets if word pairs where the first word is an abbreviation are considered as collocations.
+    ///
+    /// Defaults to 'FALSE'
+    pub fn set_include_abbrev_collocs(mut self, yes: bool) -> Self {
+        self.include_abbrev_collocs = yes;
+        self
+    }
+
+    /// Calculates and returns parameters for sentence boundary detection as
+    /// derived from training, finalizing the training first if needed.
+    pub fn get_params(&mut self) -> &PunktParameters {
+        if !self.finalized {
+            self.finalize_training();
+        }
+        &self.params
+    }
+
+    /// Consumes the trainer, returning the finalized parameters.
+    pub fn into_params(mut self) -> PunktParameters {
+        self.get_params();
+        self.params
+    }
+
+    /// Collects training data from a given text. If finalize is True, it
+    /// will determine all the parameters for sentence boundary detection. If
+    /// not, this will be delayed until get_params() or finalize_training() is
+    /// called.
+    pub fn train(&mut self, text: &str, finalize: bool) {
+        self.train_tokens(tokenize_words(text));
+        if finalize {
+            self.finalize_training();
+        }
+    }
+
+    fn train_tokens(&mut self, mut tokens: Vec<PunktToken>) {
+        self.finalized = false;
+
+        // Find the frequency of each case-normalized type.  (Don't
+        // strip off final periods.)  Also keep track of the number of
+        // tokens that end in periods.
+        for aug_tok in &tokens {
+            *self.type_fdist.entry(aug_tok.typ.clone()).or_insert(0) += 1;
+            if aug_tok.period_final {
+                self.num_period_toks += 1;
+            }
+        }
+
+        // Look for new abbreviations, and for types that no longer are
+        let unique_types: HashSet<String> = tokens.iter().map(|t| t.typ.clone()).collect();
+        for (abbr, score, is_add) in self.reclassify_abbrev_types(&unique_types) {
+            if score >= Self::ABBREV {
+                if is_add {
+                    self.params.abbrev_types.insert(abbr);
+                }
+            } else if !is_add {
+                self.params.abbrev_types.remove(&abbr);
+            }
+        }
+
+        // Make a preliminary pass through the document, marking likely
+        // sentence breaks, abbreviations, and ellipsis tokens.
+        annotate_first_pass(&mut tokens, &self.params);
+
+        // Check what contexts each word type can appear in, given the
+        // case of its first letter.
+        self.get_orthography_data(&tokens);
+
+        // We need total number of sentence breaks to find sentence starters
+        self.sentbreak_count += tokens.iter().filter(|t| t.sentbreak).count();
+
+        // The remaining heuristics relate to pairs of tokens where the first
+        // ends in a period.
+        for pair in tokens.windows(2) {
+            let (aug_tok1, aug_tok2) = (&pair[0], &pair[1]);
+            if !aug_tok1.period_final {
+                continue;
+            }
+
+            // Is the first token a rare abbreviation?
+            if self.is_rare_abbrev_type(aug_tok1, aug_tok2) {
+                self.params
+                    .abbrev_types
+                    .insert(aug_tok1.type_no_period().to_string());
+            }
+
+            // Does second token have a high likelihood of starting a sentence?
+            if Self::is_potential_sent_starter(aug_tok2, aug_tok1) {
+                *self
+                    .sent_starter_fdist
+                    .entry(aug_tok2.typ.clone())
+                    .or_insert(0) += 1;
+            }
+
+            // Is this bigram a potential collocation?
+            if self.is_potential_collocation(aug_tok1, aug_tok2) {
+                let bigram = (
+                    aug_tok1.type_no_period().to_string(),
+                    aug_tok2.type_no_sentperiod().to_string(),
+                );
+                *self.collocation_fdist.entry(bigram).or_insert(0) += 1;
+            }
+        }
+    }
+
+    /// Uses data that has been gathered in training to determine likely
+    /// collocations and sentence starters.
+    pub fn finalize_training(&mut self) {
+        self.params.clear_sent_starters();
+        let sent_starters = self.find_sent_starters();
+        self.params.sent_starters.extend(sent_starters);
+
+        self.params.clear_collocations();
+        let collocations = self.find_collocations();
+        self.params.collocations.extend(collocations);
+
+        self.finalized = true;
+    }
+
+    /// Collect information about whether each token type occurs
+    /// with different case patterns (i) overall, (ii) at
+    /// sentence-initial positions, and (iii) at sentence-internal
+    /// positions.
+    fn get_orthography_data(&mut self, tokens: &[PunktToken]) {
+        // 'initial' or 'internal' or 'unknown'
+        let mut context = "internal";
+
+        for aug_tok in tokens {
+            // If we encounter a paragraph break, then it's a good sign
+            // that it's a sentence break.  But err on the side of
+            // caution (by not positing a sentence break) if we just
+            // saw an abbreviation.
+            if aug_tok.parastart && context != "unknown" {
+                context = "initial";
+            }
+
+            // If we're at the beginning of a line, then we can't decide
+            // between 'internal' and 'initial'.
+            if aug_tok.linestart && context == "internal" {
+                context = "unknown";
+            }
+
+            // Find the case-normalized type of the token.  If it's a
+            // sentence-final token, strip off the period.
+            let typ = aug_tok.type_no_sentperiod();
+
+            // Update the orthographic context table.
+            let flag = match (context, aug_tok.first_upper(), aug_tok.first_lower()) {
+                ("initial", true, _) => ORTHO_BEG_UC,
+                ("internal", true, _) => ORTHO_MID_UC,
+                ("unknown", true, _) => ORTHO_UNK_UC,
+                ("initial", _, true) => ORTHO_BEG_LC,
+                ("internal", _, true) => ORTHO_MID_LC,
+                ("unknown", _, true) => ORTHO_UNK_LC,
+                _ => 0,
+            };
+            if flag != 0 {
+                self.params.add_ortho_context(typ, flag);
+            }
+
+            // Decide whether the next word is at a sentence boundary.
+            context = if aug_tok.sentbreak {
+                if !(aug_tok.is_number() || aug_tok.is_initial()) {
+                    "initial"
+                } else {
+                    "unknown"
+                }
+            } else if aug_tok.ellipsis || aug_tok.abbr {
+                "unknown"
+            } else {
+                "internal"
+            };
+        }
+    }
+
+    /// (Re)classifies each given token if
+    ///   - it is period-final and not a known abbreviation; or
+    ///   - it is not period-final and is otherwise a known abbreviation
+    ///
+    /// by checking whether its previous classification still holds according
+    /// to the heuristics of section 3.
+    /// Returns (abbr, score, is_add) triples, where abbr is the type in question,
+    /// score is its log-likelihood with penalties applied, and is_add specifies
+    /// whether the present type is a candidate for inclusion or exclusion as an
+    /// abbreviation, such that:
+    ///   - (is_add and score >= 0.3)    suggests a new abbreviation; and
+    ///   - (not is_add and score < 0.3) suggests excluding an abbreviation.
+    fn reclassify_abbrev_types(&self, types: &HashSet<String>) -> Vec<(String, f64, bool)> {
+        let mut result = Vec::new();
+        for typ in types {
+            // Check some basic conditions, to rule out words that are
+            // clearly not abbrev_types.
+            if !RE_NON_PUNCT.is_match(typ) || typ == "##number##" {
+                continue;
+            }
+
+            let (typ, is_add) = if let Some(without_period) = typ.strip_suffix('.') {
+                if self.params.abbrev_types.contains(typ) {
+                    continue;
+                }
+                (without_period, true)
+            } else {
+                if !self.params.abbrev_types.contains(typ) {
+                    continue;
+                }
+                (typ.as_str(), false)
+            };
+
+            // Count how many periods & nonperiods are in the
+            // candidate.
+            let num_periods = typ.matches('.').count() + 1;
+            let num_nonperiods = typ.chars().count() + 1 - num_periods;
+
+            // Let <a> be the candidate without the period, and <b>
+            // be the period.  Find a log likelihood ratio that
+            // indicates whether <ab> occurs as a single unit (high
+            // value of log_likelihood), or as two independent units <a> and
+            // <b> (low value of log_likelihood).
+            let count_with_period = self.type_fdist[&format!("{}.", typ)];
+            let count_without_period = self.type_fdist[&typ.to_string()];
+            let log_likelihood = dunning_log_likelihood(
+                (count_with_period + count_without_period) as f64,
+                self.num_period_toks as f64,
+                count_with_period as f64,
+                self.type_fdist.total::<usize>() as f64,
+            );
+
+            // Apply three scaling factors to 'tweak' the basic log
+            // likelihood ratio:
+            //   F_length: long word -> less likely to be an abbrev
+            //   F_periods: more periods -> more likely to be an abbrev
+            //   F_penalty: penalize occurrences w/o a period
+            let f_length = (-(num_nonperiods as f64)).exp();
+            let f_periods = num_periods as f64;
+            let f_penalty = (num_nonperiods as f64).powf(-(count_without_period as f64));
+            let score = log_likelihood * f_length * f_periods * f_penalty;
+
+            result.push((typ.to_string(), score, is_add));
+        }
+        result
+    }
+
+    /// A word type is counted as a rare abbreviation if...
+    ///   - it's not already marked as an abbreviation
+    ///   - it occurs fewer than ABBREV_BACKOFF times
+    ///   - either it is followed by a sentence-internal punctuation
+    ///     mark, *or* it is followed by a lower-case word that
+    ///     sometimes appears with upper case, but never occurs with
+    ///     lower case at the beginning of sentences.
+    fn is_rare_abbrev_type(&self, cur_tok: &PunktToken, next_tok: &PunktToken) -> bool {
+        if cur_tok.abbr || !cur_tok.sentbreak {
+            return false;
+        }
+
+        // Find the case-normalized type of the token.  If it's
+        // a sentence-final token, strip off the period.
+        let typ = cur_tok.type_no_sentperiod();
+
+        // Proceed only if the type hasn't been categorized as an
+        // abbreviation already, and is sufficiently rare...
+        let mut without_last = typ.chars();
+        without_last.next_back();
+        let count =
+            self.type_fdist[&typ.to_string()] + self.type_fdist[&without_last.as_str().to_string()];
+        if self.params.abbrev_types.contains(typ) || count >= Self::ABBREV_BACKOFF {
+            return false;
+        }
+
+        // Record this token as an abbreviation if the next
+        // token is a sentence-internal punctuation mark.
+        if next_tok
+            .tok
+            .chars()
+            .next()
+            .is_some_and(|c| INTERNAL_PUNCTUATION.contains(&c))
+        {
+            return true;
+        }
+
+        // Record this type as an abbreviation if the next
+        // token...  (i) starts with a lower case letter,
+        // (ii) sometimes occurs with an uppercase letter,
+        // and (iii) never occus with an uppercase letter
+        // sentence-internally.
+        if next_tok.first_lower() {
+            let typ2ortho_context = self.params.ortho_context(next_tok.type_no_sentperiod());
+            if (typ2ortho_context & ORTHO_BEG_UC) != 0 && (typ2ortho_context & ORTHO_MID_UC) == 0 {
+                return true;
+            }
+        }
+
+        false
+    }
+
+    /// Returns true if the token pair may be a collocation, where the first
+    /// token is a number or initial followed by a word.
+    fn is_potential_collocation(&self, aug_tok1: &PunktToken, aug_tok2: &PunktToken) -> bool {
+        (self.include_all_collocs
+            || (self.include_abbrev_collocs && aug_tok1.abbr)
+            || (aug_tok1.sentbreak && (aug_tok1.is_number() || aug_tok1.is_initial())))
+            && aug_tok1.is_non_punct()
+            && aug_tok2.is_non_punct()
+    }
+
+    /// Generates likely collocations and their log-likelihood.
+    fn find_collocations(&self) -> Vec<(String, String)> {
+        let n = self.type_fdist.total::<usize>() as f64;
+        let mut result = Vec::new();
+        for ((typ1, typ2), &col_count) in self.collocation_fdist.iter() {
+            if self.params.sent_starters.contains(typ2) {
+                continue;
+            }
+
+            let typ1_count = self.type_fdist[typ1] + self.type_fdist[&format!("{}.", typ1)];
+            let typ2_count = self.type_fdist[typ2] + self.type_fdist[&format!("{}.", typ2)];
+            if typ1_count > 1
+                && typ2_count > 1
+                && Self::MIN_COLLOC_FREQ < col_count
+                && col_count <= typ1_count.min(typ2_count)
+            {
+                let log_likelihood =
+                    col_log_likelihood(typ1_count as f64, typ2_count as f64, col_count as f64, n);
+                // Filter out the not-so-collocative
+                if log_likelihood >= Self::COLLOCATION
+                    && n / typ1_count as f64 > typ2_count as f64 / col_count as f64
+                {
+                    result.push((typ1.clone(), typ2.clone()));
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns True given a token and the token that precedes it if it
+    /// seems clear that the token is beginning a sentence.
+    fn is_potential_sent_starter(cur_tok: &PunktToken, prev_tok: &PunktToken) -> bool {
+        // If a token (i) is preceded by a sentece break that is
+        // not a potential ordinal number or initial, and (ii) is
+        // alphabetic, then it is a a sentence-starter.
+        prev_tok.sentbreak && !(prev_tok.is_number() || prev_tok.is_initial()) && cur_tok.is_alpha()
+    }
+
+    /// Uses collocation heuristics for each candidate token to
+    /// determine if it frequently starts sentences.
+    fn find_sent_starters(&self) -> Vec<String> {
+        let n = self.type_fdist.total::<usize>() as f64;
+        let mut result = Vec::new();
+        for (typ, &typ_at_break_count) in self.sent_starter_fdist.iter() {
+            if typ.is_empty() {
+                continue;
+            }
+
+            let typ_count = self.type_fdist[typ] + self.type_fdist[&format!("{}.", typ)];
+            if typ_count < typ_at_break_count {
+                // needed after freq_threshold
+                continue;
+            }
+
+            let log_likelihood = col_log_likelihood(
+                self.sentbreak_count as f64,
+                typ_count as f64,
+                typ_at_break_count as f64,
+                n,
+            );
+
+            if log_likelihood >= Self::SENT_STARTER
+                && n / self.sentbreak_count as f64 > typ_count as f64 / typ_at_break_count as f64
+            {
+                result.push(typ.clone());
+            }
+        }
+        result
+    }
+}
+
+/// A function that calculates the modified Dunning log-likelihood
+/// ratio scores for abbreviation candidates.  The details of how
+/// this works is available in the paper.
+fn dunning_log_likelihood(count_a: f64, count_b: f64, count_ab: f64, n: f64) -> f64 {
+    let p1 = count_b / n;
+    let p2 = 0.99;
+
+    let null_hypo = count_ab * p1.ln() + (count_a - count_ab) * (1.0 - p1).ln();
+    let alt_hypo = count_ab * f64::ln(p2) + (count_a - count_ab) * (1.0 - p2).ln();
+
+    let likelihood = null_hypo - alt_hypo;
+
+    -2.0 * likelihood
+}
+
+/// A function that will just compute log-likelihood estimate, in
+/// the original paper it's described in algorithm 6 and 7.
+///
+/// This *should* be the original Dunning log-likelihood values,
+/// unlike the previous log_l function where it used modified
+/// Dunning log-likelihood values
+fn col_log_likelihood(count_a: f64, count_b: f64, count_ab: f64, n: f64) -> f64 {
+    let p = count_b / n;
+    let p1 = count_ab / count_a;
+    let p2 = if n == count_a {
+        1.0
+    } else {
+        (count_b - count_ab) / (n - count_a)
+    };
+
+    // Python raises a ValueError for the log of zero, which is treated as no contribution
+    let valid = p > 0.0 && p < 1.0;
+    let summand1 = if valid {
+        count_ab * p.ln() + (count_a - count_ab) * (1.0 - p).ln()
+    } else {
+        0.0
+    };
+    let summand2 = if valid {
+        (count_b - count_ab) * p.ln() + (n - count_a - count_b + count_ab) * (1.0 - p).ln()
+    } else {
+        0.0
+    };
+    let summand3 = if count_a == count_ab || p1 <= 0.0 || p1 >= 1.0 {
+        0.0
+    } else {
+        count_ab * p1.ln() + (count_a - count_ab) * (1.0 - p1).ln()
+    };
+    let summand4 = if count_b == count_ab || p2 <= 0.0 || p2 >= 1.0 {
+        0.0
+    } else {
+        (count_b - count_ab) * p2.ln() + (n - count_a - count_b + count_ab) * (1.0 - p2).ln()
+    };
+
+    let likelihood = summand1 + summand2 - summand3 - summand4;
+
+    -2.0 * likelihood
+}
+
+//****************************************************************************************************************************
+//  Punkt Sentence Tokenizer
+//****************************************************************************************************************************
+
+/// A sentence tokenizer which uses an unsupervised algorithm to build
+/// a model for abbreviation words, collocations, and words that start
+/// sentences; and then uses that model to find sentence boundaries.
+/// This approach has been shown to work well for many European
+/// languages.
+///
+/// Spans are the byte offsets of the sentences within the input string.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::punkt::PunktSentenceTokenizer;
+/// let text = "Mr. Smith went to Washington. He met Dr. Watson there! Did he stay?";
+/// let tokenizer = PunktSentenceTokenizer::english();
+/// let expected = vec!["Mr. Smith went to Washington.", "He met Dr. Watson there!", "Did he stay?"];
+/// # assert_eq!(tokenizer.tokenize(text), expected);
+/// # assert_eq!(tokenizer.span_tokenize(text), vec![(0, 29), (30, 54), (55, 67)]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PunktSentenceTokenizer {
+    params: PunktParameters,
+    realign_boundaries: bool,
+}
+impl TokenizerI for PunktSentenceTokenizer {
+    /// Given a text, returns a list of the sentences in that text.
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
+    }
+
+    /// Given a text, returns a list of the (start, end) spans of sentences
+    /// in the text.
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        let slices = self.slices_from_text(sent);
+        if self.realign_boundaries {
+            Self::realign(sent, slices)
+        } else {
+            slices
+                .into_iter()
+                .filter(|(start, end)| start < end)
+                .collect()
+        }
+    }
+}
+impl Default for PunktSentenceTokenizer {
+    fn default() -> Self {
+        Self::new(PunktParameters::default())
+    }
+}
+impl PunktSentenceTokenizer {
+    /// Initialize a [PunktSentenceTokenizer] using the given parameters
+    pub fn new(params: PunktParameters) -> Self {
+        Self {
+            params,
+            realign_boundaries: true,
+        }
+    }
+
+    /// Initialize a [PunktSentenceTokenizer] using the built in English parameters,
+    /// see [PunktParameters::english]
+    pub fn english() -> Self {
+        Self::new(PunktParameters::english())
+    }
+
+    /// Initialize a [PunktSentenceTokenizer] with the parameters learned from `train_text`
+    pub fn train(train_text: &str) -> Self {
+        let mut trainer = PunktTrainer::new();
+        trainer.train(train_text, true);
+        Self::new(trainer.into_params())
+    }
+
+    /// Sets if sentence breaks are realigned so closing punctuation
+    /// following the period, like a closing quote, is kept with the sentence.
+    ///
+    /// Defaults to 'TRUE'
+    pub fn set_realign_boundaries(mut self, yes: bool) -> Self {
+        self.realign_boundaries = yes;
+        self
+    }
+
+    /// Returns the parameters used to find the sentence boundaries
+    pub fn params(&self) -> &PunktParameters {
+        &self.params
+    }
+
+    /// Given a text, find the matches of potential sentence breaks,
+    /// alongside the contexts surrounding these sentence breaks.
+    ///
+    /// The context is the word preceding the sentence ending, through to the
+    /// end of the following token.  Potential breaks whose preceding word
+    /// overlaps the previous context are skipped.
+    fn match_potential_end_contexts<'t>(&self, text: &'t str) -> Vec<(PeriodContext, &'t str)> {
+        let mut result = Vec::new();
+        let mut previous_slice = (0, 0);
+        let mut previous_match: Option<PeriodContext> = None;
+        for m in period_contexts(text) {
+            // Get the slice of the previous word
+            let before_text = &text[previous_slice.1..m.start];
+            let prev_word_start = match before_text.rfind(|c: char| c.is_ascii_whitespace()) {
+                Some(index) if index > 0 => previous_slice.1 + index + 1,
+                _ => previous_slice.0,
+            };
+            let prev_word_slice = (prev_word_start, m.start);
+
+            // If the previous slice does not overlap with this slice, then
+            // we can yield the previous match and slice. If there is an overlap,
+            // then we do not yield the previous match and slice.
+            if let Some(prev) = previous_match.take() {
+                if previous_slice.1 <= prev_word_slice.0 {
+                    let context = &text[previous_slice.0..prev.after_end];
+                    result.push((prev, context));
+                }
+            }
+            previous_match = Some(m);
+            previous_slice = prev_word_slice;
+        }
+
+        // Yield the last match and context, if it exists
+        if let Some(prev) = previous_match {
+            let context = &text[previous_slice.0..prev.after_end];
+            result.push((prev, context));
+        }
+        result
+    }
+
+    fn slices_from_text(&self, text: &str) -> Vec<Token> {
+        let mut result = Vec::new();
+        let mut last_break = 0;
+        for (m, context) in self.match_potential_end_contexts(text) {
+            if self.text_contains_sentbreak(context) {
+                result.push((last_break, m.end));
+                // next sentence starts after whitespace, or at following punctuation
+                last_break = m.next_tok.unwrap_or(m.end);
+            }
+        }
+        // The last sentence should not contain trailing whitespace.
+        result.push((last_break, text.trim_end().len().max(last_break)));
+        result
+    }
+
+    /// Attempts to realign punctuation that falls after the period but
+    /// should otherwise be included in the same sentence.
+    ///
+    /// For example: "(Sent1.) Sent2." will otherwise be split as
+    /// ``["(Sent1.", ") Sent2."]``, this method will produce ``["(Sent1.)", "Sent2."]``.
+    fn realign(text: &str, slices: Vec<Token>) -> Vec<Token> {
+        let mut result = Vec::new();
+        let mut realign = 0;
+        for (i, &(start, end)) in slices.iter().enumerate() {
+            let sentence1 = (start + realign, end);
+            match slices.get(i + 1) {
+                None => {
+                    if sentence1.0 < sentence1.1 {
+                        result.push(sentence1);
+                    }
+                }
+                Some(&(start2, end2)) => {
+                    let sentence2 = &text[start2..end2];
+                    if let Some(len) = boundary_realignment(sentence2) {
+                        let closing = sentence2[..len].trim_end().len();
+                        result.push((sentence1.0, start2 + closing));
+                        realign = len;
+                    } else {
+                        realign = 0;
+                        if sentence1.0 < sentence1.1 {
+                            result.push(sentence1);
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    /// Returns True if the given text includes a sentence break.
+    fn text_contains_sentbreak(&self, text: &str) -> bool {
+        let tokens = self.annotate_tokens(tokenize_words(text));
+        // The last token is ignored
+        tokens.iter().rev().skip(1).any(|t| t.sentbreak)
+    }
+
+    /// Given a set of tokens augmented with markers for line-start and
+    /// paragraph-start, returns those tokens with full annotation
+    /// including predicted sentence breaks.
+    fn annotate_tokens<'t>(&self, mut tokens: Vec<PunktToken<'t>>) -> Vec<PunktToken<'t>> {
+        // Make a preliminary pass through the document, marking likely
+        // sentence breaks, abbreviations, and ellipsis tokens.
+        annotate_first_pass(&mut tokens, &self.params);
+
+        // Make a second pass through the document, using token context
+        // information to change our preliminary decisions about where
+        // sentence breaks, abbreviations, and ellipsis occurs.
+        for i in 1..tokens.len() {
+            let (first, second) = tokens.split_at_mut(i);
+            self.second_pass_annotation(&mut first[i - 1], &second[0]);
+        }
+        tokens
+    }
+
+    /// Performs token-based classification over a pair of contiguous tokens
+    /// updating the first.
+    fn second_pass_annotation(&self, aug_tok1: &mut PunktToken, aug_tok2: &PunktToken) {
+        // Is it the last token? We can't do anything then.
+        if !aug_tok1.period_final {
+            // We only care about words ending in periods.
+            return;
+        }
+        let typ = aug_tok1.type_no_period().to_string();
+        let next_typ = aug_tok2.type_no_sentperiod().to_string();
+        let tok_is_initial = aug_tok1.is_initial();
+
+        // [4.1.2. Collocation Heuristic] If there's a
+        // collocation between the word before and after the
+        // period, then label tok as an abbreviation and NOT
+        // a sentence break. Note that collocations with
+        // frequent sentence starters as their second word are
+        // excluded in training.
+        if self
+            .params
+            .collocations
+            .contains(&(typ.clone(), next_typ.clone()))
+        {
+            aug_tok1.sentbreak = false;
+            aug_tok1.abbr = true;
+            return;
+        }
+
+        // [4.2. Token-Based Reclassification of Abbreviations] If
+        // the token is an abbreviation or an ellipsis, then decide
+        // whether we should *also* classify it as a sentbreak.
+        if (aug_tok1.abbr || aug_tok1.ellipsis) && !tok_is_initial {
+            // [4.1.1. Orthographic Heuristic] Check if there's
+            // orthogrpahic evidence about whether the next word
+            // starts a sentence or not.
+            if self.ortho_heuristic(aug_tok2) == Some(true) {
+                aug_tok1.sentbreak = true;
+                return;
+            }
+
+            // [4.1.3. Frequent Sentence Starter Heruistic] If the
+            // next word is capitalized, and is a member of the
+            // frequent-sentence-starters list, then label tok as a
+            // sentence break.
+            if aug_tok2.first_upper() && self.params.sent_starters.contains(&next_typ) {
+                aug_tok1.sentbreak = true;
+                return;
+            }
+        }
+
+        // [4.3. Token-Based Detection of Initials and Ordinals]
+        // Check if any initials or ordinals tokens that are marked
+        // as sentbreaks should be reclassified as abbreviations.
+        if tok_is_initial || typ == "##number##" {
+            // [4.1.1. Orthographic Heuristic] Check if there's
+            // orthogrpahic evidence about whether the next word
+            // starts a sentence or not.
+            let is_sent_starter = self.ortho_heuristic(aug_tok2);
+
+            if is_sent_starter == Some(false) {
+                aug_tok1.sentbreak = false;
+                aug_tok1.abbr = true;
+                return;
+            }
+
+            // Special heuristic for initials: if orthogrpahic
+            // heuristic is unknown, and next word is always
+            // capitalized, then mark as abbrev (eg: J. Bach).
+            if is_sent_starter.is_none()
+                && tok_is_initial
+                && aug_tok2.first_upper()
+                && (self.params.ortho_context(&next_typ) & ORTHO_LC) == 0
+            {
+                aug_tok1.sentbreak = false;
+                aug_tok1.abbr = true;
+            }
+        }
+    }
+
+    /// Decide whether the given token is the first token in a sentence,
+    /// returning [None] if unknown.
+    fn ortho_heuristic(&self, aug_tok: &PunktToken) -> Option<bool> {
+        // Sentences don't start with punctuation marks:
+        if PUNCTUATION.contains(&aug_tok.tok) {
+            return Some(false);
+        }
+
+        let ortho_context = self.params.ortho_context(aug_tok.type_no_sentperiod());
+
+        // If the word is capitalized, occurs at least once with a
+        // lower case first letter, and never occurs with an upper case
+        // first letter sentence-internally, then it's a sentence starter.
+        if aug_tok.first_upper()
+            && (ortho_context & ORTHO_LC) != 0
+            && (ortho_context & ORTHO_MID_UC) == 0
+        {
+            return Some(true);
+        }
+
+        // If the word is lower case, and either (a) we've seen it used
+        // with upper case, or (b) we've never seen it used
+        // sentence-initially with lower case, then it's not a sentence
+        // starter.
+        if aug_tok.first_lower()
+            && ((ortho_context & ORTHO_UC) != 0 || (ortho_context & ORTHO_BEG_LC) == 0)
+        {
+            return Some(false);
+        }
+
+        // Otherwise, we're not sure.
+        None
+    }
+}
+
+#[test]
+fn test_punkt_word_tokenize() {
+    let result = word_tokenize("Hello, (world)... I'm here -- \"ok\" . . . done.");
+    let expected = vec![
+        "Hello", ",", "(", "world", ")", "...", "I", "'m", "here", "--", "\"", "ok", "\"", ". . .",
+        "done.",
+    ];
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn test_punkt_span_tokenize() {
+    let tokenizer = PunktSentenceTokenizer::english();
+    let text =
+        "(Sent1.) Sent2.  \"Is it?\" he asked. Yes! The U.S. economy, e.g. jobs, grew.\n\nNew para";
+    let expected = vec![
+        "(Sent1.)",
+        "Sent2.",
+        "\"Is it?\"",
+        "he asked.",
+        "Yes!",
+        "The U.S. economy, e.g. jobs, grew.",
+        "New para",
+    ];
+    assert_eq!(tokenizer.tokenize(text), expected);
+    for ((start, end), sent) in tokenizer.span_tokenize(text).into_iter().zip(expected) {
+        assert_eq!(&text[start..end], sent);
+    }
+
+    let text = "Wait... what?  Café prices rose 3.5 per cent. Déjà vu.  ";
+    assert_eq!(
+        tokenizer.tokenize(text),
+        vec![
+            "Wait... what?",
+            "Café prices rose 3.5 per cent.",
+            "Déjà vu."
+        ]
+    );
+}
+
+#[test]
+fn test_punkt_trainer() {
+    let text = "The meeting is with Dr. Watson today. Dr. Watson is late. We wait for him. \
+                The room is at St. Mary hospital. He said so. "
+        .repeat(10);
+    let tokenizer = PunktSentenceTokenizer::train(&text);
+    assert!(tokenizer.params().abbrev_types().contains("dr"));
+    assert!(tokenizer.params().abbrev_types().contains("st"));
+    assert_eq!(
+        tokenizer.tokenize("I saw Dr. Watson. He waved."),
+        vec!["I saw Dr. Watson.", "He waved."]
+    );
+}
+
+#[test]
+fn test_punkt_english() {
+    // The sentences found by the English Punkt tokenizer of NLTK
+    let tokenizer = PunktSentenceTokenizer::english();
+    let text = "Punkt knows that the periods in Mr. Smith and Johann S. Bach\n\
+                do not mark sentence boundaries.  And sometimes sentences\n\
+                can start with non-capitalized words.  i is a good variable\n\
+                name.";
+    assert_eq!(
+        tokenizer.tokenize(text),
+        vec![
+            "Punkt knows that the periods in Mr. Smith and Johann S. Bach\ndo not mark sentence boundaries.",
+            "And sometimes sentences\ncan start with non-capitalized words.",
+            "i is a good variable\nname."
+        ]
+    );
+    let cases: [(&str, &[&str]); 11] = [
+        (
+            "this is a test. . new sentence.",
+            &["this is a test.", ".", "new sentence."],
+        ),
+        ("This. . . That", &["This.", ".", ".", "That"]),
+        ("This..... That", &["This..... That"]),
+        ("This... That", &["This... That"]),
+        ("This.. . That", &["This.. .", "That"]),
+        ("This. .. That", &["This.", ".. That"]),
+        ("This. ,. That", &["This.", ",.", "That"]),
+        ("This!!! That", &["This!!!", "That"]),
+        ("This! That", &["This!", "That"]),
+        (
+            "1. This is R .\n2. This is A .\n3. That's all",
+            &["1.", "This is R .", "2.", "This is A .", "3.", "That's all"],
+        ),
+        ("Hello.\tThere", &["Hello.", "There"]),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(tokenizer.tokenize(text), expected);
+    }
+
+    // Common words ending a sentence aren't taken for abbreviations
+    let cases: [(&str, &[&str]); 4] = [
+        (
+            "The plane took to the air. Smith followed.",
+            &["The plane took to the air.", "Smith followed."],
+        ),
+        (
+            "He wore a hat. Jones laughed.",
+            &["He wore a hat.", "Jones laughed."],
+        ),
+        (
+            "This is v2.0. Version three is next.",
+            &["This is v2.0.", "Version three is next."],
+        ),
+        (
+            "Mr. Holmes met Dr. Watson, i.e. his friend.",
+            &["Mr. Holmes met Dr. Watson, i.e. his friend."],
+        ),
+    ];
+    for (text, expected) in cases {
+        assert_eq!(tokenizer.tokenize(text), expected);
+    }
+
+    let params = PunktParameters::english();
+    assert!(params.abbrev_types().contains("mr"));
+    assert!(!params.collocations().is_empty());
+    assert!(params.ortho_context("however") & ORTHO_BEG_UC != 0);
+}
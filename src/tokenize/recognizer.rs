@@ -0,0 +1,303 @@
+//! Token Recognizers
+//!
+//! A ``Recognizer`` finds the spans of URLs, emails, IP addresses, dates, times,
+//! currency amounts and numbers in a text, and a ``ProtectedTokenizer`` keeps them
+//! whole while another tokenizer splits the rest of the text:
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::recognizer::ProtectedTokenizer;
+//! # use lib_nltk::tokenize::regexp::WordPunctTokenizer;
+//! let s = "Good muffins cost $3.88 at https://example.com/shop?id=7, order by 12/24/2024.";
+//! let result = WordPunctTokenizer().tokenize(s);
+//! # assert_eq!(result.len(), 28);
+//! let result = ProtectedTokenizer::new(WordPunctTokenizer()).tokenize(s);
+//! # assert_eq!(result, vec!["Good", "muffins", "cost", "$3.88", "at", "https://example.com/shop?id=7", ",",
+//! #     "order", "by", "12/24/2024", "."]);
+//! ```
+
+use regex::Regex;
+
+use super::api::TokenizerI;
+use super::util::{Token, TokenizeError};
+
+/// The kinds of the tokens found by a [Recognizer]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityKind {
+    /// A web address, starting with its scheme (``http://``, ``https://``, ``ftp://``) or ``www.``
+    Url,
+    /// An email address
+    Email,
+    /// An IPv4 address
+    Ip,
+    /// A date, as ``2024-12-24``, ``12/24/2024`` or ``24.12.2024``
+    Date,
+    /// A time of day, as ``10:30``, ``10:30:15`` or ``10:30 pm``
+    Time,
+    /// A number with a currency symbol before or after it, as ``$3.88`` or ``12,50 €``
+    Currency,
+    /// A number, with thousands separators and decimals, as ``1,000,000.5``
+    Number,
+}
+
+// The number of a currency amount, with thousands separators and decimals
+const AMOUNT: &str = r"(?:\d{1,3}(?:,\d{3})+|\d+)(?:[.,]\d+)?";
+
+impl EntityKind {
+    /// All the kinds, in the order they are tried when the tokens of several kinds
+    /// start at the same offset
+    pub const ALL: [EntityKind; 7] = [
+        EntityKind::Url,
+        EntityKind::Email,
+        EntityKind::Ip,
+        EntityKind::Date,
+        EntityKind::Time,
+        EntityKind::Currency,
+        EntityKind::Number,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            EntityKind::Url => "url",
+            EntityKind::Email => "email",
+            EntityKind::Ip => "ip",
+            EntityKind::Date => "date",
+            EntityKind::Time => "time",
+            EntityKind::Currency => "currency",
+            EntityKind::Number => "number",
+        }
+    }
+
+    fn pattern(self) -> String {
+        match self {
+            EntityKind::Url => {
+                r#"(?i:\b(?:https?://|ftp://|www\.))[^\s<>"']*[^\s<>"'.,;:!?()\[\]{}]"#.to_string()
+            }
+            EntityKind::Email => r"\b[\w.%+-]+@[\w-]+(?:\.[\w-]+)*\.[[:alpha:]]{2,}\b".to_string(),
+            EntityKind::Ip => {
+                r"\b(?:(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\.){3}(?:25[0-5]|2[0-4]\d|1\d\d|[1-9]?\d)\b"
+                    .to_string()
+            }
+            EntityKind::Date => {
+                r"\b(?:\d{4}-\d{1,2}-\d{1,2}|\d{1,2}/\d{1,2}/\d{2,4}|\d{1,2}\.\d{1,2}\.\d{4})\b"
+                    .to_string()
+            }
+            EntityKind::Time => {
+                r"\b(?:[01]?\d|2[0-3]):[0-5]\d(?::[0-5]\d)?(?:\s?[AaPp]\.?[Mm]\.?)?\b".to_string()
+            }
+            EntityKind::Currency => format!(r"\p{{Sc}}\s?{0}\b|\b{0}\s?\p{{Sc}}", AMOUNT),
+            EntityKind::Number => r"\b(?:\d{1,3}(?:,\d{3})+|\d+)(?:\.\d+)?\b".to_string(),
+        }
+    }
+}
+
+/// Finds the tokens of the given kinds in a text.
+///
+/// The tokens don't overlap: the token starting first wins, and of the tokens starting
+/// at the same offset, the one of the first kind in [EntityKind::ALL].
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::recognizer::{EntityKind, Recognizer};
+/// let s = "Mail admin@example.org from 10.0.0.1 before 9:45 am, 1,250.5 GB left";
+/// let result = Recognizer::new().recognize(s);
+/// # assert_eq!(result, vec![(EntityKind::Email, (5, 22)), (EntityKind::Ip, (28, 36)),
+/// #     (EntityKind::Time, (44, 51)), (EntityKind::Number, (53, 60))]);
+/// let result = Recognizer::with_kinds(&[EntityKind::Number]).unwrap().recognize(s);
+/// # assert_eq!(result.len(), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recognizer {
+    kinds: Vec<EntityKind>,
+    regex: Regex,
+}
+
+impl Default for Recognizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Recognizer {
+    /// Initializes a [Recognizer] finding the tokens of every kind
+    pub fn new() -> Self {
+        Self::with_kinds(&EntityKind::ALL).unwrap()
+    }
+
+    /// Initializes a [Recognizer] finding the tokens of *kinds* only.
+    ///
+    /// Returns [TokenizeError::InvalidConfig] if there are no kinds.
+    pub fn with_kinds(kinds: &[EntityKind]) -> Result<Self, TokenizeError> {
+        let kinds: Vec<EntityKind> = EntityKind::ALL
+            .into_iter()
+            .filter(|kind| kinds.contains(kind))
+            .collect();
+        if kinds.is_empty() {
+            return Err(TokenizeError::InvalidConfig(
+                "recognizer: no kinds of tokens".to_string(),
+            ));
+        }
+        let pattern = kinds
+            .iter()
+            .map(|kind| format!("(?P<{}>{})", kind.name(), kind.pattern()))
+            .collect::<Vec<_>>()
+            .join("|");
+        Ok(Self {
+            kinds,
+            regex: Regex::new(&pattern).map_err(TokenizeError::InvalidPattern)?,
+        })
+    }
+
+    /// Returns the kinds of the tokens found
+    pub fn kinds(&self) -> &[EntityKind] {
+        &self.kinds
+    }
+
+    /// Returns the tokens found in *text*, with their kinds and spans
+    pub fn recognize(&self, text: &str) -> Vec<(EntityKind, Token)> {
+        self.regex
+            .captures_iter(text)
+            .filter_map(|caps| {
+                let m = caps.get(0)?;
+                let kind = self
+                    .kinds
+                    .iter()
+                    .find(|kind| caps.name(kind.name()).is_some())?;
+                Some((*kind, (m.start(), m.end())))
+            })
+            .collect()
+    }
+}
+
+/// A tokenizer keeping the tokens found by a [Recognizer] whole,
+/// and splitting the text between them with another tokenizer.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::recognizer::{EntityKind, ProtectedTokenizer, Recognizer};
+/// # use lib_nltk::tokenize::regexp::WordPunctTokenizer;
+/// let s = "Opens 2024-06-01 at 08:00 (www.example.com).";
+/// let tokenizer = ProtectedTokenizer::new(WordPunctTokenizer());
+/// let result: Vec<_> = tokenizer
+///     .tagged_span_tokenize(s)
+///     .into_iter()
+///     .map(|(kind, (start, end))| (kind, &s[start..end]))
+///     .collect();
+/// # assert_eq!(result, vec![(None, "Opens"), (Some(EntityKind::Date), "2024-06-01"), (None, "at"),
+/// #     (Some(EntityKind::Time), "08:00"), (None, "("), (Some(EntityKind::Url), "www.example.com"), (None, ").")]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ProtectedTokenizer<T> {
+    tokenizer: T,
+    recognizer: Recognizer,
+}
+
+impl<T: TokenizerI> ProtectedTokenizer<T> {
+    /// Initializes a [ProtectedTokenizer] keeping the tokens of every kind whole,
+    /// and splitting the rest of the text with *tokenizer*.
+    pub fn new(tokenizer: T) -> Self {
+        Self {
+            tokenizer,
+            recognizer: Recognizer::new(),
+        }
+    }
+
+    /// Sets the recognizer finding the tokens kept whole.
+    ///
+    /// Defaults to a recognizer of every kind of token
+    pub fn set_recognizer(mut self, recognizer: Recognizer) -> Self {
+        self.recognizer = recognizer;
+        self
+    }
+
+    /// Returns the spans of the tokens of *sent*, each with the kind of the token if it
+    /// was found by the recognizer, or `None` if it was found by the tokenizer.
+    pub fn tagged_span_tokenize(&self, sent: &str) -> Vec<(Option<EntityKind>, Token)> {
+        let mut result = Vec::new();
+        let mut index = 0;
+        let entities = self.recognizer.recognize(sent);
+        for (kind, span) in entities
+            .into_iter()
+            .map(|(kind, span)| (Some(kind), span))
+            .chain([(None, (sent.len(), sent.len()))])
+        {
+            result.extend(
+                self.tokenizer
+                    .span_tokenize(&sent[index..span.0])
+                    .into_iter()
+                    .filter(|(start, end)| start < end)
+                    .map(|(start, end)| (None, (index + start, index + end))),
+            );
+            if kind.is_some() {
+                result.push((kind, span));
+            }
+            index = span.1;
+        }
+        result
+    }
+}
+
+impl<T: TokenizerI> TokenizerI for ProtectedTokenizer<T> {
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
+    }
+
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        self.tagged_span_tokenize(sent)
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect()
+    }
+}
+
+#[test]
+fn test_recognize() {
+    fn kinds(text: &str) -> Vec<(EntityKind, &str)> {
+        Recognizer::new()
+            .recognize(text)
+            .into_iter()
+            .map(|(kind, (start, end))| (kind, &text[start..end]))
+            .collect()
+    }
+    assert_eq!(
+        kinds("See <HTTP://a.example/x?q=1&r=2>."),
+        vec![(EntityKind::Url, "HTTP://a.example/x?q=1&r=2")]
+    );
+    assert_eq!(
+        kinds("€1,000,000.00 or 12,50 € or 3"),
+        vec![
+            (EntityKind::Currency, "€1,000,000.00"),
+            (EntityKind::Currency, "12,50 €"),
+            (EntityKind::Number, "3")
+        ]
+    );
+    assert_eq!(
+        kinds("24.12.2024 23:59:59 999.1.1.1"),
+        vec![
+            (EntityKind::Date, "24.12.2024"),
+            (EntityKind::Time, "23:59:59"),
+            (EntityKind::Number, "999.1"),
+            (EntityKind::Number, "1.1")
+        ]
+    );
+    assert!(kinds("no entities here").is_empty());
+    assert!(matches!(
+        Recognizer::with_kinds(&[]),
+        Err(TokenizeError::InvalidConfig(_))
+    ));
+
+    let tokenizer = ProtectedTokenizer::new(super::simple::SpaceTokenizer)
+        .set_recognizer(Recognizer::with_kinds(&[EntityKind::Email]).unwrap());
+    assert_eq!(
+        tokenizer.tokenize("to:bob@example.com, $3.88"),
+        vec!["to:", "bob@example.com", ",", "$3.88"]
+    );
+}
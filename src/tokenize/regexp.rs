@@ -4,11 +4,29 @@
 //! For example, the following tokenizer forms tokens out of alphabetic sequences,
 //! money expressions, and any other non-whitespace sequences:
 //!
-//! **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::regexp::RegexpTokenizer;
+//! let s = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\n\nThanks.";
+//! let tokenizer = RegexpTokenizer::new(r"\w+|\$[\d\.]+|\S+").unwrap();
+//! let result = tokenizer.tokenize(s);
+//! # assert_eq!(result, vec!["Good", "muffins", "cost", "$3.88", "in", "New", "York", ".",
+//! #     "Please", "buy", "me", "two", "of", "them", ".", "Thanks", "."]);
+//! ```
 //!
 //! A ``RegexpTokenizer`` can use its regexp to match delimiters instead:
 //!
-//! **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::regexp::RegexpTokenizer;
+//! # let s = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\n\nThanks.";
+//! let tokenizer = RegexpTokenizer::builder(r"\s+").set_gaps(true).build().unwrap();
+//! let result = tokenizer.tokenize(s);
+//! # assert_eq!(result, vec!["Good", "muffins", "cost", "$3.88", "in", "New", "York.",
+//! #     "Please", "buy", "me", "two", "of", "them.", "Thanks."]);
+//! ```
 //!
 //! Note that empty tokens are not returned when the delimiter appears at
 //! the start or end of the string.
@@ -16,16 +34,42 @@
 //! The material between the tokens is discarded.  For example,
 //! the following tokenizer selects just the capitalized words:
 //!
-//! **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::regexp::RegexpTokenizer;
+//! # let s = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\n\nThanks.";
+//! let capword_tokenizer = RegexpTokenizer::new(r"[A-Z]\w+").unwrap();
+//! let result = capword_tokenizer.tokenize(s);
+//! # assert_eq!(result, vec!["Good", "New", "York", "Please", "Thanks"]);
+//! ```
 //!
 //! This module contains several subclasses of ``RegexpTokenizer``
 //! that use pre-defined regular expressions.
 //!
-//! **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::regexp::BlanklineTokenizer;
+//! # let s = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\n\nThanks.";
+//! // Uses '\s*\n\s*\n\s*':
+//! let result = BlanklineTokenizer().tokenize(s);
+//! # assert_eq!(result, vec!["Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.", "Thanks."]);
+//! ```
 //!
 //! All of the regular expression tokenizers are also available as functions:
 //!
-//! **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::regexp::{regexp_tokenize, wordpunct_tokenize};
+//! # let s = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\n\nThanks.";
+//! let result = regexp_tokenize(s, r"\w+|\$[\d\.]+|\S+", false, true).unwrap();
+//! # assert_eq!(result, vec!["Good", "muffins", "cost", "$3.88", "in", "New", "York", ".",
+//! #     "Please", "buy", "me", "two", "of", "them", ".", "Thanks", "."]);
+//! let result = wordpunct_tokenize(s);
+//! # assert_eq!(result, vec!["Good", "muffins", "cost", "$", "3", ".", "88", "in", "New", "York", ".",
+//! #     "Please", "buy", "me", "two", "of", "them", ".", "Thanks", "."]);
+//! ```
 //!
 //! Caution: The function ``regexp_tokenize()`` takes the text as its
 //! first argument, and the regular expression pattern as its second
@@ -34,89 +78,165 @@
 //! (This is for consistency with the other NLTK tokenizers.)
 
 use super::api::TokenizerI;
-use super::util::Token;
+use super::util::{Token, TokenizeError};
 
+use lazy_static::lazy_static;
 use regex::{Regex, RegexBuilder};
 
+lazy_static! {
+    static ref WHITESPACE_TOKENIZER: RegexpTokenizer = RegexpTokenizer::builder(r"\s+")
+        .set_gaps(true)
+        .build()
+        .unwrap();
+    static ref BLANKLINE_TOKENIZER: RegexpTokenizer = RegexpTokenizer::builder(r"\s*\n\s*\n\s*")
+        .set_gaps(true)
+        .build()
+        .unwrap();
+    static ref WORDPUNCT_TOKENIZER: RegexpTokenizer =
+        RegexpTokenizer::new(r"\w+|[^\w\s]+").unwrap();
+}
+
 /// A tokenizer that splits a string using a regular expression, which
 /// matches either the tokens or the separators between tokens.
 ///
 /// All text are encoded in UTF-8 in Rust, so reason to assume otherwise.
+/// The spans are the byte offsets of the tokens, as the regex crate reports its matches.
 ///
-/// # Examples
+/// A [RegexpTokenizer] is only ever made from a pattern that compiled, either with
+/// [RegexpTokenizer::new] for the default options, or with the [RegexpTokenizerBuilder]
+/// returned by [RegexpTokenizer::builder].
 ///
-/// tokenizer = RegexpTokenizer::new('\w+|\$[\d\.]+|\S+');
+/// # Examples
 ///
-#[derive(Debug)]
-pub struct RegexpTokenizer<'a> {
-    pattern: &'a str,
-    regex: Option<Regex>,
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::regexp::RegexpTokenizer;
+/// let tokenizer = RegexpTokenizer::builder(",").set_gaps(true).set_discard_empty(false).build().unwrap();
+/// let result = tokenizer.tokenize("a,,b,");
+/// # assert_eq!(result, vec!["a", "", "b", ""]);
+/// let result = tokenizer.span_tokenize("a,,b,");
+/// # assert_eq!(result, vec![(0, 1), (2, 2), (3, 4), (5, 5)]);
+/// assert!(RegexpTokenizer::new(r"(\w+").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegexpTokenizer {
+    regex: Regex,
     gaps: bool,
     discard_empty: bool,
-    multiline: bool,
-    match_newline: bool,
-    case_insensitive: bool,
 }
-impl<'a> TokenizerI<'a> for RegexpTokenizer<'a> {
-    fn tokenize(&self, sent: &'a str) -> Vec<&'a str> {
-        let mut result: Vec<&str> = Vec::new();
-        let mut index = 0;
-        let matches = self
-            .regex
-            .as_ref()
-            .expect("The regex must be built before you can use it.")
-            .find_iter(sent);
-        for m in matches {
-            result.push(sent.get(index..m.start()).unwrap());
-            index = m.end();
-        }
-
-        result
+impl TokenizerI for RegexpTokenizer {
+    fn tokenize<'t>(&self, sent: &'t str) -> Vec<&'t str> {
+        self.tokenize_iter(sent).collect()
     }
+
     fn span_tokenize(&self, sent: &str) -> Vec<Token> {
-        let mut result: Vec<Token> = Vec::new();
-        let mut index: usize = 0;
-        let matches = self
-            .regex
-            .as_ref()
-            .expect("The regex must be built before you can use it.")
-            .find_iter(sent);
-        for m in matches {
-            result.push((index, m.start()));
-            index = m.end();
-        }
-        result.push((index, sent.len()));
+        self.span_tokenize_iter(sent).collect()
+    }
+
+    /// Return the tokens of sent lazily, as the matches of the pattern are found.
+    fn tokenize_iter<'t>(&self, sent: &'t str) -> impl Iterator<Item = &'t str> {
+        self.span_tokenize_iter(sent)
+            .map(move |(start, end)| &sent[start..end])
+    }
 
-        result
+    /// Return the spans of the tokens of sent lazily, as the matches of the pattern are found.
+    fn span_tokenize_iter(&self, sent: &str) -> impl Iterator<Item = Token> {
+        let mut matches = self.regex.find_iter(sent);
+        // the start of the next gap token, None once the last one is returned
+        let mut index = Some(0);
+        std::iter::from_fn(move || {
+            if !self.gaps {
+                return matches.next().map(|m| (m.start(), m.end()));
+            }
+            let start = index?;
+            match matches.next() {
+                Some(m) => {
+                    index = Some(m.end());
+                    Some((start, m.start()))
+                }
+                None => {
+                    index = None;
+                    Some((start, sent.len()))
+                }
+            }
+        })
+        .filter(move |(start, end)| !self.discard_empty || start < end)
     }
 }
-impl<'a> RegexpTokenizer<'a> {
-    /// Initialize a [RegexpTokenizer] for a given pattern
+impl RegexpTokenizer {
+    /// Initialize a [RegexpTokenizer] matching the tokens with the given pattern,
+    /// using the default options of the [RegexpTokenizerBuilder].
     ///
-    /// NOTE: you NEED to compile the given pattern before you can use
-    /// Failure to compiling will throw an Panic
-    pub fn new(ptrn: &'a str) -> Self {
-        Self {
-            pattern: ptrn,
+    /// Returns [TokenizeError::InvalidPattern] if the pattern doesn't compile.
+    pub fn new(pattern: &str) -> Result<Self, TokenizeError> {
+        Self::builder(pattern).build()
+    }
+
+    /// Returns a [RegexpTokenizerBuilder] for the given pattern, to set the
+    /// options of the tokenizer before compiling the pattern.
+    pub fn builder(pattern: &str) -> RegexpTokenizerBuilder<'_> {
+        RegexpTokenizerBuilder {
+            pattern,
+            gaps: false,
+            discard_empty: true,
             multiline: true,
             match_newline: true,
             case_insensitive: false,
-            regex: None,
-            gaps: false,
-            discard_empty: false,
         }
     }
+
+    /// Returns the pattern of the tokenizer
+    pub fn pattern(&self) -> &str {
+        self.regex.as_str()
+    }
+
+    /// True if the pattern matches the separators between tokens rather than the tokens
+    pub fn gaps(&self) -> bool {
+        self.gaps
+    }
+}
+
+/// The options of a [RegexpTokenizer], compiled into one by [RegexpTokenizerBuilder::build].
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::regexp::RegexpTokenizer;
+/// let tokenizer = RegexpTokenizer::builder(r"^[a-z]+")
+///     .set_case_insensitive(true)
+///     .build()
+///     .unwrap();
+/// let result = tokenizer.tokenize("Good muffins\nNew York");
+/// # assert_eq!(result, vec!["Good", "New"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RegexpTokenizerBuilder<'a> {
+    pattern: &'a str,
+    gaps: bool,
+    discard_empty: bool,
+    multiline: bool,
+    match_newline: bool,
+    case_insensitive: bool,
+}
+impl<'a> RegexpTokenizerBuilder<'a> {
     /// True if this tokenizer's pattern should be used
     /// to find separators between tokens; False if this
     /// tokenizer's pattern should be used to find the tokens
     /// themselves.
+    ///
+    /// Defaults to 'FALSE' - the pattern matches the tokens
     pub fn set_gaps(mut self, yes: bool) -> Self {
         self.gaps = yes;
         self
     }
     /// True if any empty tokens `''`
-    /// generated by the tokenizer should be discarded.  Empty
-    /// tokens can only be generated if `_gaps == True`.
+    /// generated by the tokenizer should be discarded, either the text between
+    /// two adjacent separators, or an empty match of the pattern.
+    ///
+    /// Defaults to 'TRUE' - empty tokens are discarded
     pub fn set_discard_empty(mut self, yes: bool) -> Self {
         self.discard_empty = yes;
         self
@@ -142,44 +262,111 @@ impl<'a> RegexpTokenizer<'a> {
         self.case_insensitive = yes;
         self
     }
-    /// Builds the Regex pattern.
+    /// Compiles the pattern into a [RegexpTokenizer].
     ///
-    /// Panics if unable to compile the pattern for any reason.
-    pub fn build(mut self) -> Self {
-        let mut builder = RegexBuilder::new(self.pattern);
-        builder
+    /// Returns [TokenizeError::InvalidPattern] if the pattern doesn't compile.
+    pub fn build(self) -> Result<RegexpTokenizer, TokenizeError> {
+        let regex = RegexBuilder::new(self.pattern)
             .unicode(true)
             .multi_line(self.multiline)
             .dot_matches_new_line(self.match_newline)
-            .case_insensitive(self.case_insensitive);
-        match builder.build() {
-            Ok(good) => self.regex = Some(good),
-            Err(e) => {
-                panic!("{:?}", e)
-            }
-        }
-        self
+            .case_insensitive(self.case_insensitive)
+            .build()
+            .map_err(TokenizeError::InvalidPattern)?;
+        Ok(RegexpTokenizer {
+            regex,
+            gaps: self.gaps,
+            discard_empty: self.discard_empty,
+        })
     }
 }
 
 /// Tokenize a string on whitespace (space, tab, newline).
 /// In general, users should use the [std::str::SplitWhitespace] method instead.
 #[allow(non_snake_case)]
-pub fn WhitespaceTokenizer() -> RegexpTokenizer<'static> {
-    RegexpTokenizer::new(r"\s+").build()
+pub fn WhitespaceTokenizer() -> RegexpTokenizer {
+    WHITESPACE_TOKENIZER.clone()
 }
 
 /// Tokenize a string, treating any sequence of blank lines as a delimiter.
 /// Blank lines are defined as lines containing no characters, except for
 /// space or tab characters.
 #[allow(non_snake_case)]
-pub fn BlanklineTokenizer() -> RegexpTokenizer<'static> {
-    RegexpTokenizer::new(r"\s*\n\s*\n\s*").build()
+pub fn BlanklineTokenizer() -> RegexpTokenizer {
+    BLANKLINE_TOKENIZER.clone()
 }
 
 /// Tokenize a text into a sequence of alphabetic and
 /// non-alphabetic characters, using the regexp ``\w+|[^\w\s]+``.
 #[allow(non_snake_case)]
-pub fn WordPunctTokenizer() -> RegexpTokenizer<'static> {
-    RegexpTokenizer::new(r"\w+|[^\w\s]+").build()
+pub fn WordPunctTokenizer() -> RegexpTokenizer {
+    WORDPUNCT_TOKENIZER.clone()
+}
+
+/// Return a tokenized copy of *text*, using a [RegexpTokenizer] for the *pattern*.
+///
+/// - gaps: true if the pattern matches the separators between tokens
+/// - discard_empty: true if the empty tokens are discarded
+///
+/// Returns [TokenizeError::InvalidPattern] if the pattern doesn't compile.
+pub fn regexp_tokenize<'t>(
+    text: &'t str,
+    pattern: &str,
+    gaps: bool,
+    discard_empty: bool,
+) -> Result<Vec<&'t str>, TokenizeError> {
+    let tokenizer = RegexpTokenizer::builder(pattern)
+        .set_gaps(gaps)
+        .set_discard_empty(discard_empty)
+        .build()?;
+    Ok(tokenizer.tokenize(text))
+}
+
+/// Return the paragraphs of *text*, using the [BlanklineTokenizer].
+pub fn blankline_tokenize(text: &str) -> Vec<&str> {
+    BLANKLINE_TOKENIZER.tokenize(text)
+}
+
+/// Return the words and punctuation of *text*, using the [WordPunctTokenizer].
+pub fn wordpunct_tokenize(text: &str) -> Vec<&str> {
+    WORDPUNCT_TOKENIZER.tokenize(text)
+}
+
+#[test]
+fn test_regexp_tokenize() {
+    let s = "  Good muffins\tcost $3.88  ";
+    let tokenizer = WhitespaceTokenizer();
+    assert_eq!(
+        tokenizer.tokenize(s),
+        vec!["Good", "muffins", "cost", "$3.88"]
+    );
+    assert_eq!(
+        tokenizer.span_tokenize(s),
+        vec![(2, 6), (7, 14), (15, 19), (20, 25)]
+    );
+
+    let tokenizer = RegexpTokenizer::builder(r"\s+")
+        .set_gaps(true)
+        .set_discard_empty(false)
+        .build()
+        .unwrap();
+    assert_eq!(
+        tokenizer.tokenize(s),
+        vec!["", "Good", "muffins", "cost", "$3.88", ""]
+    );
+
+    // empty matches are tokens too, unless they are discarded
+    let tokens = regexp_tokenize(",ab,,c", r"\w*", false, false).unwrap();
+    assert_eq!(tokens, vec!["", "ab", "", "c"]);
+    let tokens = regexp_tokenize(",ab,,c", r"\w*", false, true).unwrap();
+    assert_eq!(tokens, vec!["ab", "c"]);
+
+    assert_eq!(
+        blankline_tokenize("\n\nOne\n \nTwo\n\n"),
+        vec!["One", "Two"]
+    );
+    assert!(matches!(
+        regexp_tokenize(s, "[", false, true),
+        Err(TokenizeError::InvalidPattern(_))
+    ));
 }
@@ -0,0 +1,452 @@
+//! Tokenizer Registry
+//!
+//! A ``TokenizerRegistry`` builds tokenizers by name, from a map of their options,
+//! so that the tokenizer of a pipeline can be picked by its configuration:
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use std::collections::HashMap;
+//! # use lib_nltk::tokenize::registry::build_tokenizer;
+//! let config = HashMap::from([
+//!     ("pattern".to_string(), r"\s+".to_string()),
+//!     ("gaps".to_string(), "true".to_string()),
+//! ]);
+//! let tokenizer = build_tokenizer("regexp", &config).unwrap();
+//! let result = tokenizer.tokenize("Good muffins  cost $3.88");
+//! # assert_eq!(result, vec!["Good", "muffins", "cost", "$3.88"]);
+//! ```
+//!
+//! The built-in tokenizers and their options (all optional, unless stated otherwise) are:
+//!
+//! | name | tokenizer | options |
+//! |------|-----------|---------|
+//! | `space` | [SpaceTokenizer] | |
+//! | `tab` | [TabTokenizer] | |
+//! | `char` | [CharTokenizer] | |
+//! | `line` | [LineTokenizer] | `blanklines` (`discard`, `keep` or `discard-eof`) |
+//! | `whitespace` | [WhitespaceTokenizer] | |
+//! | `blankline` | [BlanklineTokenizer] | |
+//! | `wordpunct` | [WordPunctTokenizer] | |
+//! | `regexp` | [RegexpTokenizer] | `pattern` (required), `gaps`, `discard_empty`, `multiline`, `match_newline`, `case_insensitive` |
+//! | `sexpr` | [SExprTokenizer] | `parens` |
+//! | `nltk_word` | [NLTKWordTokenizer] | `convert_parentheses` |
+//! | `toktok` | [ToktokTokenizer] | `escape` |
+//! | `tweet` | [TweetTokenizer] | `preserve_case`, `reduce_len`, `strip_handles`, `match_phone_numbers` |
+//! | `punkt` | [PunktSentenceTokenizer] | `language`, `params` (the path of a file written by [PunktParameters::save]), `realign_boundaries` |
+//! | `sonority` | [SyllableTokenizer] | |
+//! | `bpe` | [BPETokenizer] | `vocab` (required, the path of a file written by [BPETokenizer::save]) |
+//! | `cjk` | [CJKTokenizer] | `lexicon` (the path of a file of one word per line), `direction` (`forward`, `backward` or `bidirectional`) |
+//! | `unigram` | [UnigramTokenizer] | `vocab` (required, the path of a file written by [UnigramTokenizer::save]) |
+//! | `texttiling` | [TextTilingTokenizer] | `w`, `k`, `similarity_method` (`block_comparison` or `vocabulary_introduction`), `smoothing_width`, `smoothing_rounds`, `cutoff_policy` (`lc` or `hc`) |
+//!
+//! The values of the options are strings, booleans are `true` or `false`.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::str::FromStr;
+
+use lazy_static::lazy_static;
+
+use super::api::TokenizerI;
+use super::bpe::BPETokenizer;
+use super::casual::TweetTokenizer;
+use super::cjk::{CJKTokenizer, MatchDirection};
+use super::destructive::NLTKWordTokenizer;
+use super::punkt::{PunktParameters, PunktSentenceTokenizer};
+use super::regexp::{BlanklineTokenizer, RegexpTokenizer, WhitespaceTokenizer, WordPunctTokenizer};
+use super::sexpr::SExprTokenizer;
+use super::simple::{BlanklineMode, CharTokenizer, LineTokenizer, SpaceTokenizer, TabTokenizer};
+use super::sonority::SyllableTokenizer;
+use super::texttiling::{CutoffPolicy, SimilarityMethod, TextTilingTokenizer};
+use super::toktok::ToktokTokenizer;
+use super::unigram::UnigramTokenizer;
+use super::util::TokenizeError;
+
+lazy_static! {
+    static ref BUILTIN_REGISTRY: TokenizerRegistry = TokenizerRegistry::default();
+}
+
+/// A tokenizer built by a [TokenizerRegistry]
+pub type BoxedTokenizer = Box<dyn TokenizerI + Send + Sync>;
+
+/// The options of a tokenizer, by name
+pub type TokenizerConfig = HashMap<String, String>;
+
+type Constructor =
+    Box<dyn Fn(&mut TokenizerOptions) -> Result<BoxedTokenizer, TokenizeError> + Send + Sync>;
+
+/// The options given to the constructor of a tokenizer, parsed as they are read.
+///
+/// Every option has to be read by the constructor, the registry returns
+/// [TokenizeError::InvalidConfig] for an option that wasn't, so that a misspelled
+/// option isn't silently ignored.
+#[derive(Debug)]
+pub struct TokenizerOptions<'c> {
+    name: &'c str,
+    config: &'c TokenizerConfig,
+    read: HashSet<&'c str>,
+}
+
+impl<'c> TokenizerOptions<'c> {
+    /// Returns the value of the option *key*, if it is given
+    pub fn get(&mut self, key: &str) -> Option<&'c str> {
+        let (key, value) = self.config.get_key_value(key)?;
+        self.read.insert(key);
+        Some(value)
+    }
+
+    /// Returns the value of the option *key*, or [TokenizeError::InvalidConfig] if it isn't given
+    pub fn require(&mut self, key: &str) -> Result<&'c str, TokenizeError> {
+        self.get(key).ok_or_else(|| {
+            TokenizeError::InvalidConfig(format!("{}: missing option '{}'", self.name, key))
+        })
+    }
+
+    /// Returns the value of the option *key* parsed into a `T`, or *default* if it isn't given.
+    ///
+    /// Returns [TokenizeError::InvalidConfig] if the value doesn't parse.
+    pub fn parse<T: FromStr>(&mut self, key: &str, default: T) -> Result<T, TokenizeError> {
+        match self.get(key) {
+            Some(value) => value.trim().parse().map_err(|_| {
+                TokenizeError::InvalidConfig(format!(
+                    "{}: invalid value '{}' for option '{}'",
+                    self.name, value, key
+                ))
+            }),
+            None => Ok(default),
+        }
+    }
+
+    /// Returns the value of the option *key* as one of the *choices*, or *default* if it isn't given.
+    ///
+    /// Returns [TokenizeError::InvalidConfig] if the value isn't one of the choices.
+    pub fn choice<T: Copy>(
+        &mut self,
+        key: &str,
+        choices: &[(&str, T)],
+        default: T,
+    ) -> Result<T, TokenizeError> {
+        match self.get(key) {
+            Some(value) => choices
+                .iter()
+                .find(|(choice, _)| choice.eq_ignore_ascii_case(value.trim()))
+                .map(|&(_, choice)| choice)
+                .ok_or_else(|| {
+                    TokenizeError::InvalidConfig(format!(
+                        "{}: invalid value '{}' for option '{}'",
+                        self.name, value, key
+                    ))
+                }),
+            None => Ok(default),
+        }
+    }
+}
+
+/// Builds tokenizers by name, see the [module](self) for the built-in tokenizers.
+///
+/// # Example
+///
+/// Registering a tokenizer of our own:
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use std::collections::HashMap;
+/// # use lib_nltk::tokenize::regexp::RegexpTokenizer;
+/// # use lib_nltk::tokenize::registry::TokenizerRegistry;
+/// let mut registry = TokenizerRegistry::default();
+/// registry.register("capwords", |options| {
+///     let min = options.parse("min_length", 2)?;
+///     Ok(Box::new(RegexpTokenizer::new(&format!(r"\b[A-Z]\w{{{},}}", min - 1))?))
+/// });
+/// let config = HashMap::from([("min_length".to_string(), "4".to_string())]);
+/// let tokenizer = registry.build("capwords", &config).unwrap();
+/// let result = tokenizer.tokenize("Good muffins cost $3.88 in New York.");
+/// # assert_eq!(result, vec!["Good", "York"]);
+/// assert!(registry.build("capword", &config).is_err());
+/// ```
+pub struct TokenizerRegistry {
+    constructors: HashMap<String, Constructor>,
+}
+
+impl Default for TokenizerRegistry {
+    /// A registry of the built-in tokenizers
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register("space", |_| Ok(Box::new(SpaceTokenizer)));
+        registry.register("tab", |_| Ok(Box::new(TabTokenizer)));
+        registry.register("char", |_| Ok(Box::new(CharTokenizer)));
+        registry.register("line", |options| {
+            let blanklines = options.choice(
+                "blanklines",
+                &[
+                    ("discard", BlanklineMode::Discard),
+                    ("keep", BlanklineMode::Keep),
+                    ("discard-eof", BlanklineMode::DiscardEof),
+                ],
+                BlanklineMode::Discard,
+            )?;
+            Ok(Box::new(LineTokenizer::new().set_blanklines(blanklines)))
+        });
+        registry.register("whitespace", |_| Ok(Box::new(WhitespaceTokenizer())));
+        registry.register("blankline", |_| Ok(Box::new(BlanklineTokenizer())));
+        registry.register("wordpunct", |_| Ok(Box::new(WordPunctTokenizer())));
+        registry.register("regexp", |options| {
+            let tokenizer = RegexpTokenizer::builder(options.require("pattern")?)
+                .set_gaps(options.parse("gaps", false)?)
+                .set_discard_empty(options.parse("discard_empty", true)?)
+                .set_multiline(options.parse("multiline", true)?)
+                .set_match_newline(options.parse("match_newline", true)?)
+                .set_case_insensitive(options.parse("case_insensitive", false)?)
+                .build()?;
+            Ok(Box::new(tokenizer))
+        });
+        registry.register("sexpr", |options| {
+            Ok(Box::new(SExprTokenizer::new(options.get("parens"), None)?))
+        });
+        registry.register("nltk_word", |options| {
+            Ok(Box::new(NLTKWordTokenizer::new().set_convert_parentheses(
+                options.parse("convert_parentheses", false)?,
+            )))
+        });
+        registry.register("toktok", |options| {
+            Ok(Box::new(
+                ToktokTokenizer::new().set_escape(options.parse("escape", true)?),
+            ))
+        });
+        registry.register("tweet", |options| {
+            Ok(Box::new(
+                TweetTokenizer::new()
+                    .set_preserve_case(options.parse("preserve_case", true)?)
+                    .set_reduce_len(options.parse("reduce_len", false)?)
+                    .set_strip_handles(options.parse("strip_handles", false)?)
+                    .set_match_phone_numbers(options.parse("match_phone_numbers", true)?),
+            ))
+        });
+        registry.register("punkt", |options| {
+            let params = match (options.get("language"), options.get("params")) {
+                (_, Some(path)) => File::open(path)
+                    .and_then(|file| PunktParameters::load(BufReader::new(file)))
+                    .map_err(|error| {
+                        TokenizeError::InvalidConfig(format!("punkt: params '{}': {}", path, error))
+                    })?,
+                (None | Some("english"), None) => PunktParameters::english(),
+                (Some(language), None) => {
+                    return Err(TokenizeError::UnsupportedLanguage(language.to_string()))
+                }
+            };
+            Ok(Box::new(
+                PunktSentenceTokenizer::new(params)
+                    .set_realign_boundaries(options.parse("realign_boundaries", true)?),
+            ))
+        });
+        registry.register("sonority", |_| Ok(Box::new(SyllableTokenizer::english())));
+        registry.register("bpe", |options| {
+            let path = options.require("vocab")?;
+            let tokenizer = File::open(path)
+                .and_then(|file| BPETokenizer::load(BufReader::new(file)))
+                .map_err(|error| {
+                    TokenizeError::InvalidConfig(format!("bpe: vocab '{}': {}", path, error))
+                })?;
+            Ok(Box::new(tokenizer))
+        });
+        registry.register("cjk", |options| {
+            let lexicon = match options.get("lexicon") {
+                Some(path) => File::open(path)
+                    .and_then(|file| BufReader::new(file).lines().collect())
+                    .map_err(|error| {
+                        TokenizeError::InvalidConfig(format!("cjk: lexicon '{}': {}", path, error))
+                    })?,
+                None => Vec::<String>::new(),
+            };
+            let direction = options.choice(
+                "direction",
+                &[
+                    ("forward", MatchDirection::Forward),
+                    ("backward", MatchDirection::Backward),
+                    ("bidirectional", MatchDirection::Bidirectional),
+                ],
+                MatchDirection::Bidirectional,
+            )?;
+            Ok(Box::new(
+                CJKTokenizer::new(&lexicon).set_direction(direction),
+            ))
+        });
+        registry.register("unigram", |options| {
+            let path = options.require("vocab")?;
+            let tokenizer = File::open(path)
+                .and_then(|file| UnigramTokenizer::load(BufReader::new(file)))
+                .map_err(|error| {
+                    TokenizeError::InvalidConfig(format!("unigram: vocab '{}': {}", path, error))
+                })?;
+            Ok(Box::new(tokenizer))
+        });
+        registry.register("texttiling", |options| {
+            let similarity_method = options.choice(
+                "similarity_method",
+                &[
+                    ("block_comparison", SimilarityMethod::BlockComparison),
+                    (
+                        "vocabulary_introduction",
+                        SimilarityMethod::VocabularyIntroduction,
+                    ),
+                ],
+                SimilarityMethod::BlockComparison,
+            )?;
+            let cutoff_policy = options.choice(
+                "cutoff_policy",
+                &[("lc", CutoffPolicy::LC), ("hc", CutoffPolicy::HC)],
+                CutoffPolicy::HC,
+            )?;
+            Ok(Box::new(
+                TextTilingTokenizer::new()
+                    .set_w(options.parse("w", 20)?)
+                    .set_k(options.parse("k", 10)?)
+                    .set_similarity_method(similarity_method)
+                    .set_smoothing_width(options.parse("smoothing_width", 2)?)
+                    .set_smoothing_rounds(options.parse("smoothing_rounds", 1)?)
+                    .set_cutoff_policy(cutoff_policy),
+            ))
+        });
+        registry
+    }
+}
+
+impl TokenizerRegistry {
+    /// Initializes an empty [TokenizerRegistry], see [TokenizerRegistry::default]
+    /// for one with the built-in tokenizers.
+    pub fn new() -> Self {
+        Self {
+            constructors: HashMap::new(),
+        }
+    }
+
+    /// Registers the *constructor* of the tokenizer *name*, replacing the
+    /// tokenizer registered under the same name if there is one.
+    pub fn register<F>(&mut self, name: &str, constructor: F)
+    where
+        F: Fn(&mut TokenizerOptions) -> Result<BoxedTokenizer, TokenizeError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.constructors
+            .insert(name.to_string(), Box::new(constructor));
+    }
+
+    /// Returns the names of the registered tokenizers, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.constructors.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Builds the tokenizer *name* with the options of *config*.
+    ///
+    /// Returns [TokenizeError::UnknownTokenizer] if there's no tokenizer of that name,
+    /// [TokenizeError::InvalidConfig] if an option is missing, invalid or unknown,
+    /// or the error of the tokenizer's constructor.
+    pub fn build(
+        &self,
+        name: &str,
+        config: &TokenizerConfig,
+    ) -> Result<BoxedTokenizer, TokenizeError> {
+        let constructor = self
+            .constructors
+            .get(name)
+            .ok_or_else(|| TokenizeError::UnknownTokenizer(name.to_string()))?;
+        let mut options = TokenizerOptions {
+            name,
+            config,
+            read: HashSet::new(),
+        };
+        let tokenizer = constructor(&mut options)?;
+
+        let mut unknown: Vec<&str> = config
+            .keys()
+            .map(String::as_str)
+            .filter(|key| !options.read.contains(key))
+            .collect();
+        unknown.sort_unstable();
+        match unknown.first() {
+            Some(key) => Err(TokenizeError::InvalidConfig(format!(
+                "{}: unknown option '{}'",
+                name, key
+            ))),
+            None => Ok(tokenizer),
+        }
+    }
+}
+
+/// Builds the built-in tokenizer *name* with the options of *config*,
+/// see [TokenizerRegistry::build].
+pub fn build_tokenizer(
+    name: &str,
+    config: &TokenizerConfig,
+) -> Result<BoxedTokenizer, TokenizeError> {
+    BUILTIN_REGISTRY.build(name, config)
+}
+
+#[test]
+fn test_build_tokenizer() {
+    let config = |options: &[(&str, &str)]| -> TokenizerConfig {
+        options
+            .iter()
+            .map(|&(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    };
+    let text = "Good muffins cost $3.88\nin New York.";
+
+    let registry = TokenizerRegistry::default();
+    for name in registry.names() {
+        let options = match name {
+            "regexp" => config(&[("pattern", r"\w+")]),
+            "bpe" | "unigram" => continue,
+            _ => config(&[]),
+        };
+        let tokenizer = registry.build(name, &options).unwrap();
+        let spans = tokenizer.span_tokenize(text);
+        assert!(spans
+            .iter()
+            .all(|&(start, end)| start <= end && end <= text.len()));
+    }
+
+    let tokenizer = build_tokenizer("sexpr", &config(&[("parens", "{}")])).unwrap();
+    assert_eq!(tokenizer.tokenize("{a b} c}"), vec!["{a b}", "c", "}"]);
+    assert!(matches!(
+        build_tokenizer("sexpr", &config(&[("strict", "false")])),
+        Err(TokenizeError::InvalidConfig(_))
+    ));
+    let tokenizer = build_tokenizer("sexpr", &Default::default()).unwrap();
+    assert_eq!(tokenizer.tokenize("(a (b)"), vec!["(a (b)"]);
+    let tokenizer = build_tokenizer(
+        "texttiling",
+        &config(&[("cutoff_policy", "LC"), ("w", "10")]),
+    );
+    assert!(tokenizer.is_ok());
+
+    assert!(matches!(
+        build_tokenizer("regexp", &config(&[])),
+        Err(TokenizeError::InvalidConfig(_))
+    ));
+    assert!(matches!(
+        build_tokenizer("regexp", &config(&[("pattern", "(")])),
+        Err(TokenizeError::InvalidPattern(_))
+    ));
+    assert!(matches!(
+        build_tokenizer("toktok", &config(&[("escape", "yes")])),
+        Err(TokenizeError::InvalidConfig(_))
+    ));
+    assert!(matches!(
+        build_tokenizer("toktok", &config(&[("escpae", "false")])),
+        Err(TokenizeError::InvalidConfig(_))
+    ));
+    assert!(matches!(
+        build_tokenizer("punkt", &config(&[("language", "german")])),
+        Err(TokenizeError::UnsupportedLanguage(_))
+    ));
+    assert!(matches!(
+        build_tokenizer("moses", &config(&[])),
+        Err(TokenizeError::UnknownTokenizer(_))
+    ));
+}
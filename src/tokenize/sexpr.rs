@@ -6,30 +6,63 @@
 //! nested parenthesized expressions), or other whitespace-separated
 //! tokens.
 //!
-//!  **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::sexpr::SExprTokenizer;
+//! let result = SExprTokenizer::default().tokenize("(a b (c d)) e f (g)");
+//! # assert_eq!(result, vec!["(a b (c d))", "e", "f", "(g)"]);
+//! ```
 //!
-//! By default, `SExprTokenizer` will raise a ``ValueError`` exception if
+//! By default, `SExprTokenizer::try_tokenize` will return a [TokenizeError] if
 //! used to tokenize an expression with non-matching parentheses:
 //!
-//!  **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::sexpr::SExprTokenizer;
+//! # use lib_nltk::tokenize::util::TokenizeError;
+//! let result = SExprTokenizer::default().try_tokenize("c) d) e (f (g");
+//! # assert!(matches!(result, Err(TokenizeError::UnmatchedCloseParen(1))));
+//! ```
 //!
 //! The ``strict`` argument can be set to False to allow for
 //! non-matching parentheses.  Any unmatched close parentheses will be
 //! listed as their own s-expression; and the last partial sexpr with
-//! unmatched open parentheses will be listed as its own sexpr:
+//! unmatched open parentheses will be listed as its own sexpr.
+//! [TokenizerI::tokenize] never fails, and always tokenizes this way:
 //!
-//!  **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::sexpr::SExprTokenizer;
+//! let result = SExprTokenizer::new(None, false).unwrap().try_tokenize("c) d) e (f (g");
+//! # assert_eq!(result.unwrap(), vec!["c", ")", "d", ")", "e", "(f (g"]);
+//! let result = SExprTokenizer::default().tokenize("c) d) e (f (g");
+//! # assert_eq!(result, vec!["c", ")", "d", ")", "e", "(f (g"]);
+//! ```
 //!
 //! The characters used for open and close parentheses may be customized
 //! using the ``parens`` argument to the `SExprTokenizer` constructor:
 //!
-//!  **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::sexpr::SExprTokenizer;
+//! let result = SExprTokenizer::new("{}", None).unwrap().tokenize("{a b {c d}} e f {g}");
+//! # assert_eq!(result, vec!["{a b {c d}}", "e", "f", "{g}"]);
+//! ```
 //!
 //! The s-expression tokenizer is also available as a function:
 //!
-//!  **example**
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::sexpr::sexpr_tokenize;
+//! let result = sexpr_tokenize("(a b (c d)) e f (g)").unwrap();
+//! # assert_eq!(result, vec!["(a b (c d))", "e", "f", "(g)"]);
+//! ```
 
 use super::api::TokenizerI;
+use super::util::{Token, TokenizeError};
 
 /// A tokenizer that divides strings into s-expressions.
 /// An s-expresion can be either:
@@ -43,43 +76,185 @@ use super::api::TokenizerI;
 ///
 /// By default, the characters ``(`` and ``)`` are treated as open and
 /// close parentheses, but alternative strings may be specified.
+///
+/// Spans are the byte offsets of the s-expressions within the input string.
 #[derive(Debug)]
-pub struct SExprTokenizer<'a> {
-    _paren: &'a str,
-    _strict: bool,
+pub struct SExprTokenizer {
+    open_paren: char,
+    close_paren: char,
+    strict: bool,
 }
-impl<'a> TokenizerI<'a> for SExprTokenizer<'_> {
+impl TokenizerI for SExprTokenizer {
     /// Return a list of s-expressions extracted from *text*.
     /// For example:
     ///
-    ///  **example**
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::api::TokenizerI;
+    /// # use lib_nltk::tokenize::sexpr::SExprTokenizer;
+    /// let result = SExprTokenizer::default().tokenize("(a b) c (d (e))");
+    /// # assert_eq!(result, vec!["(a b)", "c", "(d (e))"]);
+    /// ```
     ///
     /// All parentheses are assumed to mark s-expressions.
     /// (No special processing is done to exclude parentheses that occur
     /// inside strings, or following backslash characters.)
     ///
-    /// If the given expression contains non-matching parentheses,
-    /// then the behavior of the tokenizer depends on the ``strict``
-    /// parameter to the constructor.  If ``strict`` is ``True``, then
-    /// raise a ``ValueError``.  If ``strict`` is ``False``, then any
+    /// If the given expression contains non-matching parentheses, then any
     /// unmatched close parentheses will be listed as their own
     /// s-expression; and the last partial s-expression with unmatched open
-    /// parentheses will be listed as its own s-expression:
+    /// parentheses will be listed as its own s-expression, whatever the ``strict``
+    /// parameter to the constructor. Use [SExprTokenizer::try_tokenize] to get
+    /// the error in strict mode instead:
     ///
-    /// **example**
-    fn tokenize(&self, _sent: &'a str) -> Vec<&'a str> {
-        todo!()
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::api::TokenizerI;
+    /// # use lib_nltk::tokenize::sexpr::SExprTokenizer;
+    /// let result = SExprTokenizer::default().tokenize("c) d) e (f (g");
+    /// # assert_eq!(result, vec!["c", ")", "d", ")", "e", "(f (g"]);
+    /// ```
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
     }
 
-    fn span_tokenize(&self, _sent: &str) -> Vec<super::util::Token> {
-        todo!()
+    /// Identify the s-expressions using integer offsets [Token],
+    /// the non-matching parentheses handled as in non-strict mode
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        // never fails when not strict
+        self.spans(sent, false).unwrap_or_default()
     }
 }
-impl Default for SExprTokenizer<'_> {
+impl Default for SExprTokenizer {
     fn default() -> Self {
         SExprTokenizer {
-            _paren: r"()",
-            _strict: true,
+            open_paren: '(',
+            close_paren: ')',
+            strict: true,
+        }
+    }
+}
+impl SExprTokenizer {
+    /// Initializes an instance of the [SExprTokenizer] struct
+    /// parens: A two-character string specifying the open and close parentheses, defaults to "()"
+    /// strict: If true, then [SExprTokenizer::try_tokenize] returns an error for an ill-formed sexpr, defaults to true
+    ///
+    /// Returns [TokenizeError::ParseError] if *parens* isn't exactly two characters.
+    pub fn new<'p, P1, P2>(parens: P1, strict: P2) -> Result<Self, TokenizeError>
+    where
+        P1: Into<Option<&'p str>>,
+        P2: Into<Option<bool>>,
+    {
+        let mut chars = parens.into().unwrap_or("()").chars();
+        match (chars.next(), chars.next(), chars.next()) {
+            (Some(open_paren), Some(close_paren), None) => Ok(Self {
+                open_paren,
+                close_paren,
+                strict: strict.into().unwrap_or(true),
+            }),
+            _ => Err(TokenizeError::ParseError),
         }
     }
+
+    /// Return a list of s-expressions extracted from *text*,
+    /// or a [TokenizeError] in strict mode if the parentheses don't match.
+    pub fn try_tokenize<'a>(&self, text: &'a str) -> Result<Vec<&'a str>, TokenizeError> {
+        Ok(self
+            .try_span_tokenize(text)?
+            .into_iter()
+            .map(|(start, end)| &text[start..end])
+            .collect())
+    }
+
+    /// Return the spans of the s-expressions in *text*,
+    /// or a [TokenizeError] in strict mode if the parentheses don't match.
+    ///
+    /// - [TokenizeError::UnmatchedCloseParen] with the offset of the first close paren without a match
+    /// - [TokenizeError::UnmatchedOpenParen] with the offset of the s-expression left open
+    pub fn try_span_tokenize(&self, text: &str) -> Result<Vec<Token>, TokenizeError> {
+        self.spans(text, self.strict)
+    }
+
+    fn spans(&self, text: &str, strict: bool) -> Result<Vec<Token>, TokenizeError> {
+        let mut result = Vec::new();
+        let mut pos = 0;
+        let mut depth = 0;
+        for (index, paren) in text.char_indices() {
+            if paren != self.open_paren && paren != self.close_paren {
+                continue;
+            }
+            if depth == 0 {
+                whitespace_spans(text, pos, index, &mut result);
+                pos = index;
+            }
+            if paren == self.open_paren {
+                depth += 1;
+            } else {
+                if strict && depth == 0 {
+                    return Err(TokenizeError::UnmatchedCloseParen(index));
+                }
+                depth = usize::max(1, depth) - 1;
+                if depth == 0 {
+                    result.push((pos, index + paren.len_utf8()));
+                    pos = index + paren.len_utf8();
+                }
+            }
+        }
+        if depth > 0 {
+            if strict {
+                return Err(TokenizeError::UnmatchedOpenParen(pos));
+            }
+            result.push((pos, text.len()));
+        } else {
+            whitespace_spans(text, pos, text.len(), &mut result);
+        }
+        Ok(result)
+    }
+}
+
+// Adds the spans of the whitespace separated tokens between `start` and `end`
+fn whitespace_spans(text: &str, start: usize, end: usize, spans: &mut Vec<Token>) {
+    let mut token_start = None;
+    for (index, c) in text[start..end].char_indices() {
+        match (c.is_whitespace(), token_start) {
+            (true, Some(s)) => {
+                spans.push((start + s, start + index));
+                token_start = None;
+            }
+            (false, None) => token_start = Some(index),
+            _ => {}
+        }
+    }
+    if let Some(s) = token_start {
+        spans.push((start + s, end));
+    }
+}
+
+/// Return a list of s-expressions extracted from *text*,
+/// using the default strict [SExprTokenizer].
+pub fn sexpr_tokenize(text: &str) -> Result<Vec<&str>, TokenizeError> {
+    SExprTokenizer::default().try_tokenize(text)
+}
+
+#[test]
+fn test_sexpr_span_tokenize() {
+    let tokenizer = SExprTokenizer::default();
+    let text = "  (a (b c))d  é (f)";
+    assert_eq!(
+        tokenizer.span_tokenize(text),
+        vec![(2, 11), (11, 12), (14, 16), (17, 20)]
+    );
+    assert!(matches!(
+        tokenizer.try_span_tokenize("x (a (b) c"),
+        Err(TokenizeError::UnmatchedOpenParen(2))
+    ));
+    assert_eq!(tokenizer.tokenize("x (a (b) c"), vec!["x", "(a (b) c"]);
+    assert_eq!(tokenizer.tokenize("a) b"), vec!["a", ")", "b"]);
+    assert!(matches!(
+        SExprTokenizer::new("<", None),
+        Err(TokenizeError::ParseError)
+    ));
 }
@@ -14,6 +14,8 @@
 //! standard ``TokenizerI`` interface, and so can be used with any code
 //! that expects a tokenizer.  For example, these tokenizers can be used
 //! to specify the tokenization conventions when building a `CorpusReader`.
+//!
+//! As for every tokenizer, the spans are the byte offsets of the tokens.
 
 use unicode_segmentation::UnicodeSegmentation;
 
@@ -21,44 +23,49 @@ use super::api::{StringTokenizer, TokenizerI};
 use super::util::{string_span_tokenize, Token};
 
 /// Tokenize a string using the space character as a delimiter, which is the same as ``s.split(' ')``.
+#[derive(Debug, Default)]
 pub struct SpaceTokenizer;
-impl StringTokenizer<'_> for SpaceTokenizer {
-    #[allow(non_upper_case_globals)]
-    const _string: &'static str = " ";
+impl StringTokenizer for SpaceTokenizer {
+    fn separator(&self) -> &str {
+        " "
+    }
 }
-impl<'a> TokenizerI<'a> for SpaceTokenizer {
-    fn tokenize(&self, sent: &'a str) -> Vec<&'a str> {
-        sent.split(Self::_string).collect()
+impl TokenizerI for SpaceTokenizer {
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        sent.split(self.separator()).collect()
     }
 
     fn span_tokenize(&self, sent: &str) -> Vec<Token> {
-        string_span_tokenize(sent, Self::_string).unwrap()
+        string_span_tokenize(sent, self.separator()).unwrap()
     }
 }
 
 /// Tokenize a string use the tab character as a delimiter, the same as ``s.split('\t')``.
+#[derive(Debug, Default)]
 pub struct TabTokenizer;
-impl StringTokenizer<'_> for TabTokenizer {
-    #[allow(non_upper_case_globals)]
-    const _string: &'static str = r"\t";
+impl StringTokenizer for TabTokenizer {
+    fn separator(&self) -> &str {
+        "\t"
+    }
 }
-impl<'a> TokenizerI<'a> for TabTokenizer {
-    fn tokenize(&self, sent: &'a str) -> Vec<&'a str> {
-        sent.split(Self::_string).collect()
+impl TokenizerI for TabTokenizer {
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        sent.split(self.separator()).collect()
     }
 
     fn span_tokenize(&self, sent: &str) -> Vec<Token> {
-        string_span_tokenize(sent, Self::_string).unwrap()
+        string_span_tokenize(sent, self.separator()).unwrap()
     }
 }
 
 /// Tokenize a string into individual characters.  If this functionality is ever required directly, use [char].
 /// All characters are assumed to be in UTF-8 encoding as is default in Rust
+#[derive(Debug, Default)]
 pub struct CharTokenizer;
-impl<'a> TokenizerI<'a> for CharTokenizer {
+impl TokenizerI for CharTokenizer {
     /// Split the sentence into individual characters
     /// This is only splitting alphanumeric characters, control characters and emoji's are stripped out
-    fn tokenize(&self, sent: &'a str) -> Vec<&'a str> {
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
         sent.split(char::is_alphanumeric).collect()
     }
 
@@ -76,22 +83,100 @@ impl<'a> TokenizerI<'a> for CharTokenizer {
     }
 }
 
+/// What a [LineTokenizer] does with the blank lines, the lines of whitespace only
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlanklineMode {
+    /// Strip blank lines out of the token list
+    #[default]
+    Discard,
+    /// Leave all blank lines in the token list
+    Keep,
+    /// Strip the final line out of the token list, if it is a blank line
+    DiscardEof,
+}
+
 /// Tokenize a string into its lines, optionally discarding blank lines.
 /// This is similar to ``s.split('\n')`` and ``s.lines()``.
-#[derive(Debug)]
-pub struct LineTokenizer;
-impl<'a> TokenizerI<'a> for LineTokenizer {
-    fn tokenize(&self, sent: &'a str) -> Vec<&'a str> {
-        sent.lines().collect()
+///
+/// A line ends at a ``\n`` or a ``\r\n``, which are left out of its span,
+/// and the last line needs no line ending.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::simple::{BlanklineMode, LineTokenizer};
+/// let s = "Good muffins cost $3.88\r\nin New York.\n\n  \nThanks.\n\n";
+/// let result = LineTokenizer::new().tokenize(s);
+/// # assert_eq!(result, vec!["Good muffins cost $3.88", "in New York.", "Thanks."]);
+/// let result = LineTokenizer::new().set_blanklines(BlanklineMode::Keep).span_tokenize(s);
+/// # assert_eq!(result, vec![(0, 23), (25, 37), (38, 38), (39, 41), (42, 49), (50, 50)]);
+/// let result = LineTokenizer::new().set_blanklines(BlanklineMode::DiscardEof).tokenize(s);
+/// # assert_eq!(result, vec!["Good muffins cost $3.88", "in New York.", "", "  ", "Thanks."]);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LineTokenizer {
+    blanklines: BlanklineMode,
+}
+impl LineTokenizer {
+    /// Initialize a [LineTokenizer] discarding the blank lines
+    pub const fn new() -> Self {
+        Self {
+            blanklines: BlanklineMode::Discard,
+        }
+    }
+
+    /// Sets what is done with the blank lines.
+    ///
+    /// Defaults to [BlanklineMode::Discard]
+    pub fn set_blanklines(mut self, blanklines: BlanklineMode) -> Self {
+        self.blanklines = blanklines;
+        self
+    }
+}
+impl TokenizerI for LineTokenizer {
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
     }
 
     fn span_tokenize(&self, sent: &str) -> Vec<Token> {
         let mut result: Vec<Token> = Vec::new();
         let mut index = 0;
-        for l in sent.lines() {
-            result.push((index, index + l.len()));
+        for l in sent.split_inclusive('\n') {
+            let line = l.strip_suffix('\n').unwrap_or(l);
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            result.push((index, index + line.len()));
             index += l.len();
         }
+        let is_blank = |&(start, end): &Token| sent[start..end].trim().is_empty();
+        match self.blanklines {
+            BlanklineMode::Discard => result.retain(|span| !is_blank(span)),
+            BlanklineMode::Keep => {}
+            BlanklineMode::DiscardEof => {
+                if result.last().is_some_and(is_blank) {
+                    result.pop();
+                }
+            }
+        }
         result
     }
 }
+
+#[test]
+fn test_line_span_tokenize() {
+    let text = "00:01 start\r\n00:02 é\n\n00:03 end";
+    let tokenizer = LineTokenizer::new().set_blanklines(BlanklineMode::Keep);
+    let spans = tokenizer.span_tokenize(text);
+    assert_eq!(spans, vec![(0, 11), (13, 21), (22, 22), (23, 32)]);
+    assert_eq!(
+        tokenizer.tokenize(text),
+        vec!["00:01 start", "00:02 é", "", "00:03 end"]
+    );
+    assert_eq!(LineTokenizer::new().tokenize(text).len(), 3);
+    assert!(LineTokenizer::new().span_tokenize("").is_empty());
+    assert_eq!(tokenizer.span_tokenize("\n"), vec![(0, 0)]);
+}
@@ -0,0 +1,260 @@
+//! The Sonority Sequencing Principle (SSP) is a language agnostic algorithm proposed
+//! by Otto Jesperson in 1904. The sonorous quality of a phoneme is judged by the
+//! openness of the lips. Syllable breaks occur before troughs in sonority. For more
+//! on the SSP see Selkirk (1984).
+//!
+//! The default implementation uses the English alphabet, but the `sonority_hierarchy`
+//! can be modified to IPA or any other alphabet for the use-case. The SSP is a
+//! universal syllabification algorithm, but that does not mean it performs equally
+//! across languages. Bartlett et al. (2009) is a good benchmark for English accuracy
+//! if utilizing IPA (pg. 311).
+//!
+//! Unlike the [LegalitySyllableTokenizer](super::legality_principle::LegalitySyllableTokenizer),
+//! no corpus of the language is needed, only its sonority hierarchy.
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::sonority::SyllableTokenizer;
+//! let ssp = SyllableTokenizer::english();
+//! let result = ssp.tokenize("justification");
+//! # assert_eq!(result, vec!["jus", "ti", "fi", "ca", "tion"]);
+//! ```
+//!
+//! # References:
+//!
+//! - Otto Jespersen. 1904. Lehrbuch der Phonetik.
+//!   Leipzig, Teubner. Chapter 13, Silbe, pp. 185-203.
+//! - Elisabeth Selkirk. 1984. On the major class features and syllable theory.
+//!   In Aronoff & Oehrle (eds.) Language Sound Structure: Studies in Phonology.
+//!   Cambridge, MIT Press. pp. 107-136.
+//! - Susan Bartlett, et al. 2009. On the Syllabification of Phonemes.
+//!   In HLT-NAACL. pp. 308-316.
+
+use std::collections::HashMap;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::api::TokenizerI;
+use super::util::{Token, TokenizeError};
+
+// The default sonority hierarchy of the english alphabet, from the most to the least sonorous
+const ENGLISH_SONORITY_HIERARCHY: [&str; 4] = [
+    "aeiouy",      // vowels.
+    "lmnrw",       // nasals.
+    "zvsf",        // fricatives.
+    "bcdgtkpqxhj", // stops.
+];
+
+// Sonority of the digits and punctuation, they always break a syllable
+const BREAK: isize = -1;
+
+/// Syllabifies words based on the Sonority Sequencing Principle (SSP).
+///
+/// The phonemes are the (lower cased) graphemes of the word, so that a
+/// combining diacritic stays with its base character.
+/// Spans are the byte offsets of the syllables within the input word.
+///
+/// # Example
+///
+/// A hierarchy using IPA classes, the most sonorous level comes first
+/// and its phonemes are the vowels:
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::sonority::SyllableTokenizer;
+/// let ssp = SyllableTokenizer::new(&["aeiouɪʊəɛæ", "jw", "lr", "mnŋ", "zvðʒ", "sfθʃh", "bdg", "ptk"]).unwrap();
+/// let result = ssp.tokenize("ʃəmpæntʃ");
+/// # assert_eq!(result, vec!["ʃəm", "pæntʃ"]);
+/// let result = ssp.span_tokenize("ʃəmpæntʃ");
+/// # assert_eq!(result, vec![(0, 5), (5, 12)]);
+/// ```
+#[derive(Debug)]
+pub struct SyllableTokenizer {
+    phoneme_map: HashMap<String, isize>,
+    vowel_value: isize,
+}
+impl TokenizerI for SyllableTokenizer {
+    /// Apply the SSP to return a list of syllables.
+    /// Note: Only the first level of the hierarchy is considered to be vowels,
+    /// a word with at most one vowel is returned as a single syllable.
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
+    }
+
+    /// Identify the syllables using integer offsets [Token]
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        let values = self.assign_values(sent);
+        // if only one vowel return word
+        let vowel_count = values
+            .iter()
+            .filter(|&&(_, value)| value == self.vowel_value)
+            .count();
+        if vowel_count <= 1 {
+            return if sent.is_empty() {
+                vec![]
+            } else {
+                vec![(0, sent.len())]
+            };
+        }
+
+        let mut syllables: Vec<Token> = Vec::new();
+        // start syllable with first phoneme
+        let mut start = 0;
+        for trigram in values.windows(3) {
+            // Sonority of previous, focal and following phoneme
+            let (prev_value, focal_value, next_value) = (trigram[0].1, trigram[1].1, trigram[2].1);
+            let (focal, next) = (trigram[1].0, trigram[2].0);
+
+            // These cases trigger syllable break.
+            if focal_value == BREAK {
+                // If it's a punctuation, just break.
+                syllables.push((start, focal));
+                syllables.push((focal, next));
+                start = next;
+            } else if prev_value >= focal_value && focal_value == next_value {
+                syllables.push((start, next));
+                start = next;
+            } else if prev_value > focal_value && focal_value < next_value {
+                syllables.push((start, focal));
+                start = focal;
+            }
+        }
+        // append last phoneme
+        syllables.push((start, sent.len()));
+        syllables.retain(|(start, end)| start < end);
+
+        self.validate_syllables(sent, &syllables)
+    }
+}
+
+impl Default for SyllableTokenizer {
+    fn default() -> Self {
+        Self::english()
+    }
+}
+
+impl SyllableTokenizer {
+    /// Initializes an instance of the [SyllableTokenizer] struct
+    /// with the default english sonority hierarchy:
+    /// vowels, nasals, fricatives and stops.
+    pub fn english() -> Self {
+        Self::new(&ENGLISH_SONORITY_HIERARCHY).unwrap()
+    }
+
+    /// Initializes an instance of the [SyllableTokenizer] struct
+    /// sonority_hierarchy: the levels of phonemes, from the most to the least sonorous.
+    /// The phonemes of a level are its graphemes, `|` may be used to separate them.
+    /// The first level is the vowels.
+    ///
+    /// Returns [TokenizeError::ParseError] if the hierarchy has no vowels.
+    pub fn new<S: AsRef<str>>(sonority_hierarchy: &[S]) -> Result<Self, TokenizeError> {
+        let mut phoneme_map = HashMap::new();
+        for (i, level) in sonority_hierarchy.iter().enumerate() {
+            for phoneme in level.as_ref().graphemes(true).filter(|&c| c != "|") {
+                phoneme_map.insert(
+                    phoneme.to_lowercase(),
+                    (sonority_hierarchy.len() - i) as isize,
+                );
+            }
+        }
+        let vowel_value = sonority_hierarchy.len() as isize;
+        if !phoneme_map.values().any(|&value| value == vowel_value) {
+            return Err(TokenizeError::ParseError);
+        }
+        Ok(Self {
+            phoneme_map,
+            vowel_value,
+        })
+    }
+
+    // Assigns each phoneme its value from the sonority hierarchy.
+    // Note: Sentence/text has to be tokenized first.
+    //
+    // Returns the offset of each phoneme and its sonority.
+    fn assign_values(&self, token: &str) -> Vec<(usize, isize)> {
+        token
+            .grapheme_indices(true)
+            .map(|(i, c)| (i, self.sonority(c)))
+            .collect()
+    }
+
+    // A phoneme missing from the hierarchy gets the sonority of its base character,
+    // or is more sonorous than the vowels, except for the digits and punctuation
+    // that always break a syllable.
+    fn sonority(&self, phoneme: &str) -> isize {
+        let base = phoneme.chars().next().map(String::from).unwrap_or_default();
+        match self
+            .phoneme_map
+            .get(&phoneme.to_lowercase())
+            .or_else(|| self.phoneme_map.get(&base.to_lowercase()))
+        {
+            Some(&value) => value,
+            None if is_break(phoneme) => BREAK,
+            None => self.vowel_value + 1,
+        }
+    }
+
+    // Ensures each syllable has at least one vowel.
+    // If the following syllable doesn't have vowel, add it to the current one.
+    fn validate_syllables(&self, token: &str, syllables: &[Token]) -> Vec<Token> {
+        let mut valid_syllables: Vec<Token> = Vec::new();
+        let mut front = None;
+        for &(start, end) in syllables {
+            let syllable = &token[start..end];
+            if is_punctuation(syllable) {
+                // the syllables without vowel before it make up a syllable of their own
+                if let Some(front) = front.take() {
+                    valid_syllables.push((front, start));
+                }
+                valid_syllables.push((start, end));
+                continue;
+            }
+            let has_vowel = syllable
+                .graphemes(true)
+                .any(|c| self.sonority(c) == self.vowel_value);
+            match (has_vowel, valid_syllables.last_mut()) {
+                (false, Some(last)) => last.1 = end,
+                (false, None) => front = front.or(Some(start)),
+                (true, _) => valid_syllables.push((front.take().unwrap_or(start), end)),
+            }
+        }
+        valid_syllables
+    }
+}
+
+// Digits and punctuation get no sonority
+fn is_break(c: &str) -> bool {
+    c.chars()
+        .all(|c| c.is_ascii_digit() || c.is_ascii_punctuation())
+}
+
+fn is_punctuation(syllable: &str) -> bool {
+    let mut chars = syllable.chars();
+    matches!((chars.next(), chars.next()), (Some(c), None) if c.is_ascii_punctuation())
+}
+
+#[test]
+fn test_sonority_span_tokenize() {
+    let ssp = SyllableTokenizer::default();
+    assert_eq!(ssp.tokenize("Sentence"), vec!["Sen", "ten", "ce"]);
+    assert_eq!(
+        ssp.tokenize("self-evident"),
+        vec!["self", "-", "e", "vi", "dent"]
+    );
+    assert_eq!(ssp.span_tokenize("cafe\u{301}s"), vec![(0, 2), (2, 7)]);
+    assert_eq!(ssp.span_tokenize("rhythm"), vec![(0, 6)]);
+    assert_eq!(
+        ssp.span_tokenize("pfft-banana"),
+        vec![(0, 4), (4, 5), (5, 7), (7, 9), (9, 11)]
+    );
+    assert!(ssp.span_tokenize("").is_empty());
+    assert!(matches!(
+        SyllableTokenizer::new::<&str>(&[]),
+        Err(TokenizeError::ParseError)
+    ));
+}
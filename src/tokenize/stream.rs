@@ -0,0 +1,265 @@
+//! Streaming Tokenization
+//!
+//! A ``StreamTokenizer`` tokenizes the text read from any [BufRead], a chunk at a time,
+//! so that a corpus never has to be held in memory as a whole:
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use std::io::BufReader;
+//! # use lib_nltk::tokenize::regexp::WordPunctTokenizer;
+//! # use lib_nltk::tokenize::stream::StreamTokenizer;
+//! let reader = BufReader::new("Good muffins cost $3.88\nin New York.".as_bytes());
+//! let tokenizer = WordPunctTokenizer();
+//! let mut tokens = Vec::new();
+//! for token in StreamTokenizer::new(&tokenizer, reader).set_chunk_size(4) {
+//!     tokens.push(token.unwrap());
+//! }
+//! # assert_eq!(tokens[..4], [("Good".to_string(), (0, 4)), ("muffins".to_string(), (5, 12)),
+//! #     ("cost".to_string(), (13, 17)), ("$".to_string(), (18, 19))]);
+//! # assert_eq!(tokens[10], (".".to_string(), (35, 36)));
+//! ```
+
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Read};
+
+use super::api::TokenizerI;
+use super::util::Token;
+
+// The default number of bytes read at a time
+const CHUNK_SIZE: usize = 64 * 1024;
+// The default maximum number of bytes held back
+const MAX_TOKEN_LEN: usize = 1024 * 1024;
+
+/// An iterator over the tokens of the text read from a [BufRead], along with their
+/// spans as byte offsets from the start of the stream.
+///
+/// The text is read a chunk at a time and tokenized with [TokenizerI::span_tokenize].
+/// The last token found in a chunk may go on in the next one, so it is held back
+/// and tokenized again together with the following chunk; a token straddling the
+/// boundary between two chunks is thus returned whole. The tokens are the same
+/// as the ones of the whole text, as long as the tokenizer only needs to look as
+/// far ahead as the start of the next token (true of the [RegexpTokenizer]s,
+/// but not, for example, of a tokenizer with rules for the end of the text).
+///
+/// The text read is only tokenized once there is at least as much of it as was held
+/// back, so that a long token isn't tokenized again for every chunk. The text of a
+/// chunk without any token is dropped, and the held back text never grows past the
+/// maximum token length: a longer token is returned in pieces of that length.
+/// At most the held back text and one chunk are thus in memory at a time.
+///
+/// Reading errors and text that isn't valid UTF-8 are returned as an [io::Error],
+/// after which the iterator is done.
+///
+/// [RegexpTokenizer]: super::regexp::RegexpTokenizer
+pub struct StreamTokenizer<'t, T: ?Sized, R> {
+    tokenizer: &'t T,
+    reader: R,
+    chunk_size: usize,
+    max_token_len: usize,
+    // The text read but not tokenized for good, and its offset in the stream
+    buffer: String,
+    offset: usize,
+    // The length of the text held back the last time it was tokenized
+    held: usize,
+    // The bytes of a char cut in two by the end of the last chunk
+    partial: Vec<u8>,
+    tokens: VecDeque<(String, Token)>,
+    done: bool,
+}
+
+impl<'t, T, R> StreamTokenizer<'t, T, R>
+where
+    T: TokenizerI + ?Sized,
+    R: BufRead,
+{
+    /// Initializes a [StreamTokenizer] returning the tokens found by *tokenizer*
+    /// in the text read from *reader*.
+    pub fn new(tokenizer: &'t T, reader: R) -> Self {
+        Self {
+            tokenizer,
+            reader,
+            chunk_size: CHUNK_SIZE,
+            max_token_len: MAX_TOKEN_LEN,
+            buffer: String::new(),
+            offset: 0,
+            held: 0,
+            partial: Vec::new(),
+            tokens: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Sets the number of bytes read at a time
+    ///
+    /// Defaults to 64 KiB
+    pub fn set_chunk_size(mut self, size: usize) -> Self {
+        self.chunk_size = usize::max(size, 1);
+        self
+    }
+
+    /// Sets the maximum number of bytes of text held back, past which the held back
+    /// token is returned as it is
+    ///
+    /// Defaults to 1 MiB
+    pub fn set_max_token_len(mut self, len: usize) -> Self {
+        self.max_token_len = usize::max(len, 1);
+        self
+    }
+
+    // Reads in the next chunk, and queues the tokens found up to the last one
+    fn read_chunk(&mut self) -> io::Result<()> {
+        let mut bytes = std::mem::take(&mut self.partial);
+        let read = (&mut self.reader)
+            .take(self.chunk_size as u64)
+            .read_to_end(&mut bytes)?;
+        let eof = read == 0;
+
+        match std::str::from_utf8(&bytes) {
+            Ok(text) => self.buffer.push_str(text),
+            // a char cut in two by the end of the chunk, it is finished by the next one
+            Err(error) if error.error_len().is_none() && !eof => {
+                let valid = error.valid_up_to();
+                self.buffer
+                    .push_str(std::str::from_utf8(&bytes[..valid]).unwrap());
+                self.partial = bytes[valid..].to_vec();
+            }
+            Err(error) => return Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+        }
+
+        // wait for as much new text as was held back, unless there's too much of it
+        if !eof && self.buffer.len() < 2 * self.held && self.buffer.len() <= self.max_token_len {
+            return Ok(());
+        }
+
+        let spans = self.tokenizer.span_tokenize(&self.buffer);
+        let keep = if eof {
+            self.done = true;
+            spans.len()
+        } else {
+            spans.len().saturating_sub(1)
+        };
+        for &(start, end) in &spans[..keep] {
+            self.push_token(start, end);
+        }
+        // drop the text before the held back token, or all of it without a token
+        let mut consumed = match spans.get(keep) {
+            Some(&(start, _)) => start,
+            None => self.buffer.len(),
+        };
+        // a held back token too long is returned as it is
+        if let Some(&(start, end)) = spans.get(keep) {
+            if self.buffer.len() - start > self.max_token_len {
+                self.push_token(start, end);
+                consumed = end;
+            }
+        }
+        self.buffer.drain(..consumed);
+        self.offset += consumed;
+        self.held = self.buffer.len();
+        Ok(())
+    }
+
+    fn push_token(&mut self, start: usize, end: usize) {
+        self.tokens.push_back((
+            self.buffer[start..end].to_string(),
+            (self.offset + start, self.offset + end),
+        ));
+    }
+}
+
+impl<T, R> Iterator for StreamTokenizer<'_, T, R>
+where
+    T: TokenizerI + ?Sized,
+    R: BufRead,
+{
+    type Item = io::Result<(String, Token)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(token) = self.tokens.pop_front() {
+                return Some(Ok(token));
+            }
+            if self.done {
+                return None;
+            }
+            if let Err(error) = self.read_chunk() {
+                self.done = true;
+                return Some(Err(error));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_stream_tokenize() {
+    use super::regexp::{BlanklineTokenizer, WhitespaceTokenizer, WordPunctTokenizer};
+
+    let text = "Good muffins cost $3.88\nin Nëw York.  Pléase buy me\ntwo of them.\n\n\n 😀Thanks.";
+    for tokenizer in [
+        WhitespaceTokenizer(),
+        WordPunctTokenizer(),
+        BlanklineTokenizer(),
+    ] {
+        let expected: Vec<(String, Token)> = tokenizer
+            .span_tokenize(text)
+            .into_iter()
+            .map(|(start, end)| (text[start..end].to_string(), (start, end)))
+            .collect();
+        for chunk_size in [1, 2, 3, 7, 1000] {
+            let tokens: Vec<(String, Token)> = StreamTokenizer::new(&tokenizer, text.as_bytes())
+                .set_chunk_size(chunk_size)
+                .collect::<io::Result<_>>()
+                .unwrap();
+            assert_eq!(tokens, expected);
+        }
+    }
+
+    let tokenizer = WhitespaceTokenizer();
+    assert_eq!(StreamTokenizer::new(&tokenizer, "".as_bytes()).count(), 0);
+    let mut tokens = StreamTokenizer::new(&tokenizer, &b"ok \xFF"[..]);
+    assert!(tokens.next().unwrap().is_err());
+    assert!(tokens.next().is_none());
+}
+
+#[test]
+fn test_stream_bounded() {
+    use super::regexp::{BlanklineTokenizer, WhitespaceTokenizer};
+
+    // a single long paragraph is returned in pieces of the maximum length
+    let text = "word ".repeat(20_000);
+    let tokenizer = BlanklineTokenizer();
+    let mut stream = StreamTokenizer::new(&tokenizer, text.as_bytes())
+        .set_chunk_size(64)
+        .set_max_token_len(1000);
+    let mut tokens = Vec::new();
+    while let Some(token) = stream.next() {
+        assert!(stream.buffer.len() <= 1000 + 64);
+        tokens.push(token.unwrap());
+    }
+    assert!(tokens.len() > 1);
+    for (token, (start, end)) in &tokens {
+        assert!(token.len() <= 1000 + 64);
+        assert_eq!(token, &text[*start..*end]);
+    }
+    let rebuilt: String = tokens.iter().map(|(token, _)| token.as_str()).collect();
+    assert_eq!(rebuilt, text);
+
+    // so is a long run of text between two tokens, which is dropped
+    let text = format!("a{}b", " ".repeat(100_000));
+    let tokenizer = WhitespaceTokenizer();
+    let mut stream = StreamTokenizer::new(&tokenizer, text.as_bytes())
+        .set_chunk_size(64)
+        .set_max_token_len(1000);
+    let mut tokens = Vec::new();
+    while let Some(token) = stream.next() {
+        assert!(stream.buffer.len() <= 1000 + 64);
+        tokens.push(token.unwrap());
+    }
+    assert_eq!(
+        tokens,
+        vec![
+            ("a".to_string(), (0, 1)),
+            ("b".to_string(), (100_001, 100_002))
+        ]
+    );
+}
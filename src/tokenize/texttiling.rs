@@ -0,0 +1,520 @@
+//! TextTiling Tokenizer
+//!
+//! Tokenize a document into topical sections using the TextTiling algorithm.
+//! This algorithm detects subtopic shifts based on the analysis of lexical
+//! co-occurrence patterns.
+//!
+//! The process starts by tokenizing the text into pseudosentences of
+//! a fixed size w. Then, depending on the method used, similarity
+//! scores are assigned at sentence gaps. The algorithm proceeds by
+//! detecting the peak differences between these scores and marking
+//! them as boundaries. The boundaries are normalized to the closest
+//! paragraph break and the segmented text is returned.
+//!
+//! Reference:
+//! Marti A. Hearst. 1997. TextTiling: Segmenting Text into Multi-paragraph
+//! Subtopic Passages. Computational Linguistics, 23(1), pp. 33-64.
+//!
+//! Copyright (C) 2001-2022 NLTK Project
+//! Author: George Boutsioukis
+//! URL: <https://www.nltk.org>
+//! For license information, see LICENSE.TXT
+
+use std::collections::{HashMap, HashSet};
+
+use super::api::TokenizerI;
+use super::regexp::{BlanklineTokenizer, RegexpTokenizer};
+use super::util::Token;
+
+// Paragraph breaks closer than this to the previous one are ignored
+const MIN_PARAGRAPH: usize = 100;
+
+// The english stopwords of the NLTK stopwords corpus
+const ENGLISH_STOPWORDS: &str = "\
+    i me my myself we our ours ourselves you you're you've you'll you'd your yours yourself \
+    yourselves he him his himself she she's her hers herself it it's its itself they them \
+    their theirs themselves what which who whom this that that'll these those am is are was \
+    were be been being have has had having do does did doing a an the and but if or because \
+    as until while of at by for with about against between into through during before after \
+    above below to from up down in out on off over under again further then once here there \
+    when where why how all any both each few more most other some such no nor not only own \
+    same so than too very s t can will just don don't should should've now d ll m o re ve y \
+    ain aren aren't couldn couldn't didn didn't doesn doesn't hadn hadn't hasn hasn't haven \
+    haven't isn isn't ma mightn mightn't mustn mustn't needn needn't shan shan't shouldn \
+    shouldn't wasn wasn't weren weren't won won't wouldn wouldn't";
+
+/// The methods scoring the lexical similarity at the gaps between pseudosentences
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimilarityMethod {
+    /// Compares the word frequencies of the blocks of pseudosentences on each side of the gap
+    BlockComparison,
+    /// Counts the words introduced for the first time by the pseudosentences around the gap
+    VocabularyIntroduction,
+}
+
+/// The cutoff policies for the depth scores marking a boundary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CutoffPolicy {
+    /// Liberal cutoff, the mean minus the standard deviation of the depth scores
+    LC,
+    /// Conservative cutoff, the mean minus half the standard deviation of the depth scores
+    HC,
+}
+
+/// Tokenize a document into topical sections using the TextTiling algorithm.
+///
+/// Spans are the byte offsets of the sections in the input text. A section ends at
+/// a paragraph break, the blank lines belong to the start of the next section.
+/// A text too short to be segmented is returned as a single section.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::texttiling::TextTilingTokenizer;
+/// let tt = TextTilingTokenizer::new();
+/// let result = tt.tokenize("A single short paragraph.");
+/// # assert_eq!(result, vec!["A single short paragraph."]);
+/// ```
+#[derive(Debug)]
+pub struct TextTilingTokenizer {
+    w: usize,
+    k: usize,
+    similarity_method: SimilarityMethod,
+    stopwords: HashSet<String>,
+    smoothing_width: usize,
+    smoothing_rounds: usize,
+    cutoff_policy: CutoffPolicy,
+    paragraphs: RegexpTokenizer,
+}
+impl TokenizerI for TextTilingTokenizer {
+    /// Return a tokenized copy of *text*, where each "token" represents
+    /// a separate topic.
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
+    }
+
+    /// Identify the topical sections using integer offsets [Token]
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        if sent.is_empty() {
+            return vec![];
+        }
+        let mut result = Vec::new();
+        let mut prev = 0;
+        for boundary in self.boundaries(sent) {
+            if boundary > prev {
+                result.push((prev, boundary));
+                prev = boundary;
+            }
+        }
+        // append any text that may be remaining
+        if prev < sent.len() {
+            result.push((prev, sent.len()));
+        }
+        result
+    }
+}
+
+impl Default for TextTilingTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TextTilingTokenizer {
+    /// Initializes an instance of the [TextTilingTokenizer] struct with
+    /// pseudosentences of 20 words, blocks of 10 pseudosentences, the block comparison
+    /// method, the english stopwords, a smoothing width of 2 and the [CutoffPolicy::HC].
+    pub fn new() -> Self {
+        Self {
+            w: 20,
+            k: 10,
+            similarity_method: SimilarityMethod::BlockComparison,
+            stopwords: ENGLISH_STOPWORDS
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+            smoothing_width: 2,
+            smoothing_rounds: 1,
+            cutoff_policy: CutoffPolicy::HC,
+            paragraphs: BlanklineTokenizer(),
+        }
+    }
+
+    /// Sets the pseudosentence size
+    ///
+    /// Defaults to 20
+    pub fn set_w(mut self, w: usize) -> Self {
+        self.w = usize::max(w, 1);
+        self
+    }
+
+    /// Sets the size (in pseudosentences) of the blocks used in the block comparison method
+    ///
+    /// Defaults to 10
+    pub fn set_k(mut self, k: usize) -> Self {
+        self.k = usize::max(k, 1);
+        self
+    }
+
+    /// Sets the method used for determining similarity scores
+    ///
+    /// Defaults to [SimilarityMethod::BlockComparison]
+    pub fn set_similarity_method(mut self, method: SimilarityMethod) -> Self {
+        self.similarity_method = method;
+        self
+    }
+
+    /// Sets the words ignored when scoring the similarity, they are matched lower cased
+    ///
+    /// Defaults to the english stopwords
+    pub fn set_stopwords(mut self, stopwords: &[&str]) -> Self {
+        self.stopwords = stopwords.iter().map(|w| w.to_lowercase()).collect();
+        self
+    }
+
+    /// Sets the width of the window used by the smoothing method
+    ///
+    /// Defaults to 2
+    pub fn set_smoothing_width(mut self, width: usize) -> Self {
+        self.smoothing_width = width;
+        self
+    }
+
+    /// Sets the number of smoothing passes
+    ///
+    /// Defaults to 1
+    pub fn set_smoothing_rounds(mut self, rounds: usize) -> Self {
+        self.smoothing_rounds = rounds;
+        self
+    }
+
+    /// Sets the policy used to determine the number of boundaries
+    ///
+    /// Defaults to [CutoffPolicy::HC]
+    pub fn set_cutoff_policy(mut self, policy: CutoffPolicy) -> Self {
+        self.cutoff_policy = policy;
+        self
+    }
+
+    // Returns the offsets of the section boundaries in the text, empty
+    // if it's too short to be segmented.
+    fn boundaries(&self, text: &str) -> Vec<usize> {
+        let paragraph_breaks = self.mark_paragraph_breaks(text);
+
+        // Tokenization step starts here
+
+        // Remove punctuation
+        let nopunct_text: String = text
+            .to_lowercase()
+            .chars()
+            .filter(|c| matches!(c, 'a'..='z' | '-' | '\'' | ' ' | '\n' | '\t'))
+            .collect();
+        if self.mark_paragraph_breaks(&nopunct_text).len() < 2 {
+            // No paragraph breaks were found (text too short perhaps?)
+            return vec![];
+        }
+
+        // The morphological stemming step mentioned in the TextTile
+        // paper is not implemented.  A comment in the original C
+        // implementation states that it offers no benefit to the
+        // process.
+        let tokseqs = self.divide_to_tokensequences(&nopunct_text);
+        // End of the Tokenization step
+
+        // Lexical score determination
+        let gap_scores = match self.similarity_method {
+            SimilarityMethod::BlockComparison => self.block_comparison(&tokseqs),
+            SimilarityMethod::VocabularyIntroduction => self.vocabulary_introduction(&tokseqs),
+        };
+        let mut smooth_scores = gap_scores;
+        for _ in 0..self.smoothing_rounds {
+            smooth_scores = match smooth(&smooth_scores, self.smoothing_width + 1) {
+                Some(scores) => scores,
+                None => return vec![],
+            };
+        }
+        // End of Lexical score Determination
+
+        // Boundary identification
+        let depth_scores = depth_scores(&smooth_scores);
+        let segment_boundaries = self.identify_boundaries(&depth_scores);
+        self.normalize_boundaries(text, &segment_boundaries, &paragraph_breaks)
+    }
+
+    // Identifies the blank lines as the beginning of paragraphs
+    fn mark_paragraph_breaks(&self, text: &str) -> Vec<usize> {
+        let paragraphs = self.paragraphs.span_tokenize(text);
+        let mut last_break = 0;
+        let mut pbreaks = vec![0];
+        for &(_, end) in paragraphs.iter().take(paragraphs.len().saturating_sub(1)) {
+            if end - last_break >= MIN_PARAGRAPH {
+                pbreaks.push(end);
+                last_break = end;
+            }
+        }
+        pbreaks
+    }
+
+    // Divides the text into pseudosentences of fixed size, returning the
+    // count of each word in each of them, stopwords excluded.
+    fn divide_to_tokensequences<'t>(&self, text: &'t str) -> Vec<HashMap<&'t str, usize>> {
+        let words: Vec<&str> = text
+            .split(|c: char| !c.is_ascii_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        words
+            .chunks(self.w)
+            .map(|chunk| {
+                let mut counts = HashMap::new();
+                for word in chunk.iter().filter(|w| !self.stopwords.contains(**w)) {
+                    *counts.entry(*word).or_insert(0) += 1;
+                }
+                counts
+            })
+            .collect()
+    }
+
+    // Implements the block comparison method
+    fn block_comparison<'t>(&self, tokseqs: &[HashMap<&'t str, usize>]) -> Vec<f64> {
+        // The word frequencies in the block of pseudosentences
+        let blk_frq = |block: &[HashMap<&'t str, usize>]| {
+            let mut freqs: HashMap<&str, f64> = HashMap::new();
+            for (word, count) in block.iter().flatten() {
+                *freqs.entry(word).or_insert(0.0) += *count as f64;
+            }
+            freqs
+        };
+
+        let numgaps = tokseqs.len().saturating_sub(1);
+        (0..numgaps)
+            .map(|curr_gap| {
+                // adjust window size for boundary conditions
+                let window_size = if curr_gap + 1 < self.k {
+                    curr_gap + 1
+                } else if curr_gap + self.k > numgaps {
+                    numgaps - curr_gap
+                } else {
+                    self.k
+                };
+                let b1 = blk_frq(&tokseqs[curr_gap + 1 - window_size..curr_gap + 1]);
+                let b2 = blk_frq(&tokseqs[curr_gap + 1..curr_gap + window_size + 1]);
+
+                let score_dividend: f64 = b1
+                    .iter()
+                    .map(|(word, freq)| freq * b2.get(word).unwrap_or(&0.0))
+                    .sum();
+                let score_divisor_b1: f64 = b1.values().map(|freq| freq * freq).sum();
+                let score_divisor_b2: f64 = b2.values().map(|freq| freq * freq).sum();
+                let divisor = (score_divisor_b1 * score_divisor_b2).sqrt();
+                if divisor > 0.0 {
+                    score_dividend / divisor
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    // Implements the vocabulary introduction method, the score of a gap is one minus the
+    // number of words first seen in the pseudosentences on each side of it over their
+    // size, so that a topic shift, which brings in new words, shows as a valley.
+    fn vocabulary_introduction(&self, tokseqs: &[HashMap<&str, usize>]) -> Vec<f64> {
+        let mut seen = HashSet::new();
+        let new_words: Vec<usize> = tokseqs
+            .iter()
+            .map(|tokseq| tokseq.keys().filter(|word| seen.insert(**word)).count())
+            .collect();
+        new_words
+            .windows(2)
+            .map(|pair| 1.0 - (pair[0] + pair[1]) as f64 / (2 * self.w) as f64)
+            .collect()
+    }
+
+    // Identifies boundaries at the peaks of similarity score differences
+    fn identify_boundaries(&self, depth_scores: &[f64]) -> Vec<bool> {
+        let mut boundaries = vec![false; depth_scores.len()];
+        if depth_scores.is_empty() {
+            return boundaries;
+        }
+
+        let len = depth_scores.len() as f64;
+        let avg = depth_scores.iter().sum::<f64>() / len;
+        let stdev = (depth_scores.iter().map(|d| (d - avg).powi(2)).sum::<f64>() / len).sqrt();
+        let cutoff = match self.cutoff_policy {
+            CutoffPolicy::LC => avg - stdev,
+            CutoffPolicy::HC => avg - stdev / 2.0,
+        };
+
+        let mut depth_tuples: Vec<(f64, usize)> = depth_scores.iter().copied().zip(0..).collect();
+        depth_tuples.sort_by(|a, b| b.partial_cmp(a).unwrap());
+        let hp: Vec<usize> = depth_tuples
+            .into_iter()
+            .filter(|(depth, _)| *depth > cutoff)
+            .map(|(_, i)| i)
+            .collect();
+
+        for &i in &hp {
+            // undo if there is a boundary close already
+            boundaries[i] = !hp
+                .iter()
+                .any(|&j| i != j && i.abs_diff(j) < 4 && boundaries[j]);
+        }
+        boundaries
+    }
+
+    // Normalize the boundaries identified to the original text's paragraph breaks
+    fn normalize_boundaries(
+        &self,
+        text: &str,
+        boundaries: &[bool],
+        paragraph_breaks: &[usize],
+    ) -> Vec<usize> {
+        let mut norm_boundaries = Vec::new();
+        let (mut word_count, mut gaps_seen) = (0, 0);
+        let mut seen_word = false;
+
+        for (i, c) in text.char_indices() {
+            let char_count = i + c.len_utf8();
+            let space = matches!(c, ' ' | '\t' | '\n');
+            if space && seen_word {
+                seen_word = false;
+                word_count += 1;
+            }
+            if !space && !seen_word {
+                seen_word = true;
+            }
+            if gaps_seen < boundaries.len() && word_count > usize::max(gaps_seen * self.w, self.w) {
+                if boundaries[gaps_seen] {
+                    // find closest paragraph break
+                    let mut best_fit = text.len();
+                    let mut bestbr = 0;
+                    for &br in paragraph_breaks {
+                        if best_fit > br.abs_diff(char_count) {
+                            best_fit = br.abs_diff(char_count);
+                            bestbr = br;
+                        } else {
+                            break;
+                        }
+                    }
+                    // avoid duplicates
+                    if !norm_boundaries.contains(&bestbr) {
+                        norm_boundaries.push(bestbr);
+                    }
+                }
+                gaps_seen += 1;
+            }
+        }
+        norm_boundaries
+    }
+}
+
+// Calculates the depth of each gap, i.e. the average difference
+// between the left and right peaks and the gap's score
+fn depth_scores(scores: &[f64]) -> Vec<f64> {
+    let mut depth_scores = vec![0.0; scores.len()];
+    // clip boundaries: this holds on the rule of thumb(my thumb)
+    // that a section shouldn't be smaller than at least 2
+    // pseudosentences for small texts and around 5 for larger ones.
+    let clip = usize::min(usize::max(scores.len() / 10, 2), 5);
+
+    for index in clip..scores.len().saturating_sub(clip) {
+        let gapscore = scores[index];
+        let mut lpeak = gapscore;
+        for &score in scores[..=index].iter().rev() {
+            if score >= lpeak {
+                lpeak = score;
+            } else {
+                break;
+            }
+        }
+        let mut rpeak = gapscore;
+        for &score in &scores[index..] {
+            if score >= rpeak {
+                rpeak = score;
+            } else {
+                break;
+            }
+        }
+        depth_scores[index] = lpeak + rpeak - 2.0 * gapscore;
+    }
+    depth_scores
+}
+
+// Smooths the scores with a moving average over *window_len* scores, after
+// reflecting the scores around both ends (from the SciPy cookbook).
+// Returns [None] if there are fewer scores than the window length.
+fn smooth(x: &[f64], window_len: usize) -> Option<Vec<f64>> {
+    if x.len() < window_len {
+        return None;
+    }
+    if window_len < 3 {
+        return Some(x.to_vec());
+    }
+    let (first, last) = (x[0], x[x.len() - 1]);
+    let s: Vec<f64> = (2..=usize::min(window_len, x.len() - 1))
+        .rev()
+        .map(|i| 2.0 * first - x[i])
+        .chain(x.iter().copied())
+        .chain((1..window_len).map(|i| 2.0 * last - x[x.len() - i]))
+        .collect();
+    let offset = (window_len - 1) / 2;
+    Some(
+        (0..s.len() + 2 - 2 * window_len)
+            .map(|i| s[i + offset..i + offset + window_len].iter().sum::<f64>() / window_len as f64)
+            .collect(),
+    )
+}
+
+#[test]
+fn test_texttiling_span_tokenize() {
+    let cats = "cat fur purr kitten yarn paw whisker meow claw litter tabby nap mouse collar bowl \
+        milk tail sofa pet vet";
+    let ships = "ship sail harbor captain wave anchor deck sailor rope mast port tide hull crew \
+        voyage compass storm cargo keel oar";
+    let common = "day time people way year thing world life hand part place case week work night \
+        point home water room fact";
+    // Paragraphs mixing the words of a topic with common words
+    let mut seed: u64 = 7;
+    let mut rnd = |n: usize| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((seed >> 33) as usize) % n
+    };
+    let common: Vec<&str> = common.split_whitespace().collect();
+    let mut paragraphs = Vec::new();
+    for topic in [cats, ships] {
+        let topic: Vec<&str> = topic.split_whitespace().collect();
+        for _ in 0..6 {
+            let words: Vec<&str> = (0..40)
+                .map(|_| match rnd(2) {
+                    0 => topic[rnd(topic.len())],
+                    _ => common[rnd(common.len())],
+                })
+                .collect();
+            paragraphs.push(words.join(" ") + ".");
+        }
+    }
+    let text = paragraphs.join("\n\n");
+    let boundary = paragraphs[..6].iter().map(|p| p.len() + 2).sum::<usize>() - 2;
+
+    for method in [
+        SimilarityMethod::BlockComparison,
+        SimilarityMethod::VocabularyIntroduction,
+    ] {
+        let tt = TextTilingTokenizer::new()
+            .set_k(6)
+            .set_similarity_method(method);
+        let spans = tt.span_tokenize(&text);
+        assert!(spans.len() > 1);
+        assert_eq!(spans.first().unwrap().0, 0);
+        assert_eq!(spans.last().unwrap().1, text.len());
+        assert!(spans.windows(2).all(|pair| pair[0].1 == pair[1].0));
+        assert!(spans.iter().any(|span| span.1 == boundary));
+    }
+}
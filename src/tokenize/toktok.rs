@@ -0,0 +1,328 @@
+//! The tok-tok tokenizer is a simple, general tokenizer, where the input has one
+//! sentence per line; thus only final period is tokenized.
+//!
+//! Tok-tok has been tested on, and gives reasonably good results for English,
+//! Persian, Russian, Czech, French, German, Vietnamese, Tajik, and a few others.
+//! The input should be in UTF-8 encoding.
+//!
+//! Reference:
+//! Jon Dehdari. 2014. A Neurophysiologically-Inspired Statistical Language
+//! Model (Doctoral dissertation). Columbus, OH, USA: The Ohio State University.
+//!
+//! Copyright (C) 2001-2022 NLTK Project
+//! Author: Jon Dehdari
+//! Contributors: Liling Tan, Selcuk Ayguney, ikegami, Martijn Pieters
+//! URL: <https://www.nltk.org>
+//! For license information, see LICENSE.TXT
+
+use super::api::TokenizerI;
+use super::util::{align_found_tokens, align_tokens, Normalization, Token, TokenizeError};
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+lazy_static! {
+    // The escaped characters, for the tokens to be found in the original text
+    static ref UNESCAPE: Normalization = ESCAPES
+        .iter()
+        .fold(Normalization::new(), |normalization, (original, symbol)| {
+            normalization.add(symbol, original)
+        });
+
+    // Replace non-breaking spaces with normal spaces.
+    static ref NON_BREAKING: (Regex, &'static str) = (Regex::new("\u{00A0}").unwrap(), " ");
+
+    // Pad some funky punctuation.
+    static ref FUNKY_PUNCT_1: (Regex, &'static str) =
+        (Regex::new(r#"([،;؛¿!"\])}»›”؟¡%٪°±©®।॥…])"#).unwrap(), " $1 ");
+    // Pad more funky punctuation.
+    static ref FUNKY_PUNCT_2: (Regex, &'static str) =
+        (Regex::new(r"([({\[“‘„‚«‹「『])").unwrap(), " $1 ");
+    // Pad En dash and em dash
+    static ref EN_EM_DASHES: (Regex, &'static str) = (Regex::new("([–—])").unwrap(), " $1 ");
+
+    // Replace problematic character with numeric character reference.
+    static ref AMPERCENT: (Regex, &'static str) = (Regex::new("& ").unwrap(), "&amp; ");
+    static ref TAB: (Regex, &'static str) = (Regex::new("\t").unwrap(), " &#9; ");
+    static ref PIPE: (Regex, &'static str) = (Regex::new(r"\|").unwrap(), " &#124; ");
+
+    // Pad numbers with commas to keep them from further tokenization.
+    // Python: (?<!,)([,،])(?![,\d])
+    static ref COMMA_IN_NUM: (Regex, &'static str) = (Regex::new("([,،])").unwrap(), " $1 ");
+
+    // Just pad problematic (often neurotic) hyphen/single quote, etc.
+    static ref PROB_SINGLE_QUOTES: (Regex, &'static str) = (Regex::new("(['’`])").unwrap(), " $1 ");
+    // Group ` ` stupid quotes ' ' into a single token.
+    static ref STUPID_QUOTES_1: (Regex, &'static str) = (Regex::new(" ` ` ").unwrap(), " `` ");
+    static ref STUPID_QUOTES_2: (Regex, &'static str) = (Regex::new(" ' ' ").unwrap(), " '' ");
+
+    // Don't tokenize period unless it ends the line and that it isn't
+    // preceded by another period, e.g.
+    // "something ..." -> "something ..."
+    // "something." -> "something ."
+    // Python: (?<!\.)\.$
+    static ref FINAL_PERIOD_1: (Regex, &'static str) = (Regex::new(r"\.$").unwrap(), " .");
+    // Don't tokenize period unless it ends the line eg.
+    // " ... stuff." ->  "... stuff ."
+    // Python: (?<!\.)\.\s*(["'’»›”]) *$
+    static ref FINAL_PERIOD_2: (Regex, &'static str) =
+        (Regex::new(r#"\.\s*(["'’»›”]) *$"#).unwrap(), " . $1");
+
+    // Treat continuous commas as fake German,Czech, etc.: „
+    static ref MULTI_COMMAS: (Regex, &'static str) = (Regex::new("(,{2,})").unwrap(), " $1 ");
+    // Treat continuous dashes as fake en-dash, etc.
+    static ref MULTI_DASHES: (Regex, &'static str) = (Regex::new("(-{2,})").unwrap(), " $1 ");
+    // Treat multiple periods as a thing (eg. ellipsis)
+    static ref MULTI_DOTS: (Regex, &'static str) = (Regex::new(r"(\.{2,})").unwrap(), " $1 ");
+
+    // Pad spaces after opening punctuations, \p{Open_Punctuation} in Perl.
+    static ref OPEN_PUNCT_RE: (Regex, &'static str) = (Regex::new(r"(\p{Ps})").unwrap(), "$1 ");
+    // Pad spaces before closing punctuations, \p{Close_Punctuation} in Perl.
+    static ref CLOSE_PUNCT_RE: (Regex, &'static str) = (Regex::new(r"(\p{Pe})").unwrap(), "$1 ");
+    // Pad spaces after currency symbols, \p{Currency_Symbol} in Perl.
+    static ref CURRENCY_SYM_RE: (Regex, &'static str) = (Regex::new(r"(\p{Sc})").unwrap(), "$1 ");
+
+    // Use for tokenizing URL-unfriendly characters: [:/?#]
+    // Python: :(?!//)
+    static ref URL_FOE_1: (Regex, &'static str) = (Regex::new(":").unwrap(), " : ");
+    // Python: \?(?!\S)
+    static ref URL_FOE_2: (Regex, &'static str) = (Regex::new(r"\?").unwrap(), " ? ");
+    // in perl: m{://} or m{\S+\.\S+/\S+} or s{/}{ / }g;
+    static ref URL_FOE_3: (Regex, &'static str) =
+        (Regex::new(r"(://)[\S+\.\S+/\S+][/]").unwrap(), " / ");
+    static ref URL_FOE_4: (Regex, &'static str) = (Regex::new(" /").unwrap(), " / ");
+
+    // Merge multiple spaces.
+    static ref ONE_SPACE: (Regex, &'static str) = (Regex::new(" {2,}").unwrap(), " ");
+}
+
+// The characters escaped by the tokenizer, and the numeric character reference they are escaped to.
+const ESCAPES: [(&str, &str); 3] = [("&", "&amp;"), ("\t", "&#9;"), ("|", "&#124;")];
+
+// Substitutes the matches of the rule, Python's lookarounds aren't supported by the
+// regex crate so `keep` is given the text before and after each match instead,
+// matches it returns false for are left as is.
+fn replace_where<F>(text: &str, rule: &(Regex, &str), keep: F) -> String
+where
+    F: Fn(&str, &str) -> bool,
+{
+    let (regexp, substitution) = rule;
+    regexp
+        .replace_all(text, |caps: &Captures| {
+            let m = caps.get(0).unwrap();
+            let mut result = String::new();
+            if keep(&text[..m.start()], &text[m.end()..]) {
+                caps.expand(substitution, &mut result);
+            } else {
+                result.push_str(m.as_str());
+            }
+            result
+        })
+        .into_owned()
+}
+
+/// This is a Python port of the tok-tok.pl from
+/// <https://github.com/jonsafari/tok-tok/blob/master/tok-tok.pl>
+///
+/// By default the tokens are Penn-escaped, `&` (when followed by a space), `|` and
+/// tabs are returned as their numeric character reference (`&amp;`, `&#124;` and `&#9;`).
+/// Every token returned is either a slice of the input, or one of the escapes.
+///
+/// The spans are the byte offsets of the tokens within the input string,
+/// escapes are given the span of the original character.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::toktok::ToktokTokenizer;
+/// let toktok = ToktokTokenizer::new();
+/// let text = "Is 9.5 or 525,600 my favorite number?";
+/// let result = toktok.tokenize(text);
+/// # assert_eq!(result, vec!["Is", "9.5", "or", "525,600", "my", "favorite", "number", "?"]);
+/// let text = "The https://github.com/jonsafari/tok-tok/blob/master/tok-tok.pl is a website with/and/or slashes and sort of weird : things";
+/// let result = toktok.tokenize(text);
+/// # assert_eq!(result, vec!["The", "https://github.com/jonsafari/tok-tok/blob/master/tok-tok.pl", "is", "a", "website",
+/// #     "with/and/or", "slashes", "and", "sort", "of", "weird", ":", "things"]);
+/// let text = "\u{a1}This, is a sentence with weird\u{bb} symbols\u{2026} appearing everywhere\u{bf}";
+/// let result = toktok.tokenize(text);
+/// # assert_eq!(result, vec!["\u{a1}", "This", ",", "is", "a", "sentence", "with", "weird", "\u{bb}", "symbols",
+/// #     "\u{2026}", "appearing", "everywhere", "\u{bf}"]);
+/// ```
+#[derive(Debug)]
+pub struct ToktokTokenizer {
+    escape: bool,
+}
+impl TokenizerI for ToktokTokenizer {
+    /// Return a tokenized copy of `text`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::api::TokenizerI;
+    /// # use lib_nltk::tokenize::toktok::ToktokTokenizer;
+    /// let text = "Fish & Chips | The Pub";
+    /// let result = ToktokTokenizer::new().tokenize(text);
+    /// # assert_eq!(result, vec!["Fish", "&amp;", "Chips", "&#124;", "The", "Pub"]);
+    /// let result = ToktokTokenizer::new().set_escape(false).tokenize(text);
+    /// # assert_eq!(result, vec!["Fish", "&", "Chips", "|", "The", "Pub"]);
+    /// ```
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.align(sent).into_iter().map(|(t, _)| t).collect()
+    }
+
+    /// Returns the spans of the tokens in ``text``.
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        self.align(sent).into_iter().map(|(_, s)| s).collect()
+    }
+}
+
+impl Default for ToktokTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToktokTokenizer {
+    /// Initialize a [ToktokTokenizer] returning Penn-escaped tokens
+    pub const fn new() -> Self {
+        Self { escape: true }
+    }
+
+    /// Sets if the escaped characters are returned as their numeric character
+    /// reference (``|`` as ``&#124;``, etc), or as is.
+    ///
+    /// Defaults to 'TRUE' - the tokens are Penn-escaped
+    pub fn set_escape(mut self, yes: bool) -> Self {
+        self.escape = yes;
+        self
+    }
+
+    /// Runs the regex rules over the text, returning the munged tokens
+    fn munge(&self, text: &str) -> Vec<String> {
+        let mut text = text.to_string();
+        for rule in [&*NON_BREAKING, &*FUNKY_PUNCT_1, &*FUNKY_PUNCT_2] {
+            text = rule.0.replace_all(&text, rule.1).into_owned();
+        }
+        text = replace_where(&text, &URL_FOE_1, |_, after| !after.starts_with("//"));
+        text = replace_where(&text, &URL_FOE_2, |_, after| {
+            after.chars().next().is_none_or(char::is_whitespace)
+        });
+        for rule in [
+            &*URL_FOE_3,
+            &*URL_FOE_4,
+            &*AMPERCENT,
+            &*TAB,
+            &*PIPE,
+            &*OPEN_PUNCT_RE,
+            &*CLOSE_PUNCT_RE,
+            &*MULTI_COMMAS,
+        ] {
+            text = rule.0.replace_all(&text, rule.1).into_owned();
+        }
+        text = replace_where(&text, &COMMA_IN_NUM, |before, after| {
+            !before.ends_with(',') && !after.starts_with(|c: char| c == ',' || c.is_ascii_digit())
+        });
+        for rule in [
+            &*PROB_SINGLE_QUOTES,
+            &*STUPID_QUOTES_1,
+            &*STUPID_QUOTES_2,
+            &*CURRENCY_SYM_RE,
+            &*EN_EM_DASHES,
+            &*MULTI_DASHES,
+            &*MULTI_DOTS,
+        ] {
+            text = rule.0.replace_all(&text, rule.1).into_owned();
+        }
+        text = replace_where(&text, &FINAL_PERIOD_1, |before, _| !before.ends_with('.'));
+        text = replace_where(&text, &FINAL_PERIOD_2, |before, _| !before.ends_with('.'));
+        text = ONE_SPACE.0.replace_all(&text, ONE_SPACE.1).into_owned();
+
+        text.split_whitespace().map(String::from).collect()
+    }
+
+    /// Returns the spans of the tokens in *text*, as [TokenizerI::span_tokenize],
+    /// or [TokenizeError::MismatchError] if a token returned by the rules isn't found in it.
+    pub fn try_span_tokenize(&self, text: &str) -> Result<Vec<Token>, TokenizeError> {
+        align_tokens(&self.munge(text), text, &*UNESCAPE)
+    }
+
+    /// Lines the munged tokens back up with the input string,
+    /// returning each token along with its span.
+    ///
+    /// The rules pad and escape the tokens, and only drop characters between them,
+    /// so every token should be found in the input string; one that isn't is left out.
+    fn align<'a>(&self, sent: &'a str) -> Vec<(&'a str, Token)> {
+        let tokens = self.munge(sent);
+        let spans = align_found_tokens(&tokens, sent, &UNESCAPE);
+        tokens
+            .iter()
+            .zip(spans)
+            .filter_map(|(token, span)| {
+                let (start, end) = span?;
+                let escaped = ESCAPES.iter().find(|(_, symbol)| symbol == token);
+                match escaped {
+                    Some((_, symbol)) if self.escape => Some((*symbol, (start, end))),
+                    _ => Some((&sent[start..end], (start, end))),
+                }
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn test_toktok_span_tokenize() {
+    let toktok = ToktokTokenizer::new();
+    let text = "Tab\tseparated \u{a0}«values», cost €1,000 — or $5...";
+    assert_eq!(
+        toktok.tokenize(text),
+        vec![
+            "Tab",
+            "&#9;",
+            "separated",
+            "«",
+            "values",
+            "»",
+            ",",
+            "cost",
+            "€",
+            "1,000",
+            "—",
+            "or",
+            "$",
+            "5",
+            "..."
+        ]
+    );
+    assert_eq!(
+        toktok.span_tokenize(text),
+        vec![
+            (0, 3),
+            (3, 4),
+            (4, 13),
+            (16, 18),
+            (18, 24),
+            (24, 26),
+            (26, 27),
+            (28, 32),
+            (33, 36),
+            (36, 41),
+            (42, 45),
+            (46, 48),
+            (49, 50),
+            (50, 51),
+            (51, 54)
+        ]
+    );
+
+    // every token of the rules is kept
+    let text = "a\t\tb | c & d://e/f";
+    let munged = toktok.munge(text);
+    assert_eq!(toktok.tokenize(text), munged);
+    assert_eq!(
+        toktok.try_span_tokenize(text).unwrap(),
+        toktok.span_tokenize(text)
+    );
+}
@@ -0,0 +1,226 @@
+//! Penn Treebank Tokenizer
+//!
+//! The Treebank tokenizer uses regular expressions to tokenize text as in Penn Treebank.
+//! This implementation is a port of the tokenizer sed script written by Robert McIntyre
+//! and available at <http://www.cis.upenn.edu/~treebank/tokenizer.sed>.
+//!
+//! Only the [TreebankWordDetokenizer] is provided here, as the
+//! [NLTKWordTokenizer](super::destructive::NLTKWordTokenizer) supersedes the
+//! original Treebank tokenizer.
+
+use super::destructive::{MACINTYRE_CONTRACTIONS2, MACINTYRE_CONTRACTIONS3};
+use super::util::CONVERT_PARENTHESES;
+
+use lazy_static::lazy_static;
+use regex::{Captures, Regex};
+
+// Reverses the MacIntyre contractions, the split point `(?:)` becomes the whitespace between the tokens.
+fn compile_reversed(patterns: &[&str]) -> Vec<Regex> {
+    patterns
+        .iter()
+        .map(|p| Regex::new(&p.replace("(?:)", r"\s")).unwrap())
+        .collect()
+}
+
+lazy_static! {
+    static ref CONTRACTIONS2: Vec<Regex> = compile_reversed(&MACINTYRE_CONTRACTIONS2);
+    static ref CONTRACTIONS3: Vec<Regex> = compile_reversed(&MACINTYRE_CONTRACTIONS3);
+
+    // ending quotes
+    static ref ENDING_QUOTES: Vec<(Regex, &'static str)> = vec![
+        (Regex::new(r"([^' ])\s('ll|'LL|'re|'RE|'ve|'VE|n't|N'T) ").unwrap(), "$1$2 "),
+        (Regex::new(r"([^' ])\s('[sS]|'[mM]|'[dD]|') ").unwrap(), "$1$2 "),
+        (Regex::new(r"(\S)\s('')").unwrap(), "$1$2"),
+        // Quotes followed by no-left-padded punctuations.
+        (Regex::new(r"('')\s([.,:)\]>};%])").unwrap(), "$1$2"),
+        (Regex::new(r"''").unwrap(), "\""),
+    ];
+
+    // Handles double dashes
+    static ref DOUBLE_DASHES: Regex = Regex::new(r" -- ").unwrap();
+
+    // Undo padding on parentheses.
+    static ref PARENS_BRACKETS: Vec<(Regex, &'static str)> = vec![
+        (Regex::new(r"([\[\(\{<])\s").unwrap(), "$1"),
+        (Regex::new(r"\s([\]\)\}>])").unwrap(), "$1"),
+        (Regex::new(r"([\]\)\}>])\s([:;,.])").unwrap(), "$1$2"),
+    ];
+
+    // punctuation
+    static ref PUNCTUATION: Vec<(Regex, &'static str)> = vec![
+        (Regex::new(r"([^'])\s'\s").unwrap(), "$1' "),
+        // Strip left pad for [?!]
+        (Regex::new(r"\s([?!])").unwrap(), "$1"),
+        (Regex::new(r#"([^\.])\s(\.)([\]\)}>"']*)\s*$"#).unwrap(), "$1$2$3"),
+        // When tokenizing, [;@#$%&] are padded with whitespace regardless of
+        // whether there are spaces before or after them.
+        // But during detokenization, we need to distinguish between left/right
+        // pad, so we split this up.
+        // Left pad.
+        (Regex::new(r"([#$])\s").unwrap(), "$1"),
+        // Right pad.
+        (Regex::new(r"\s([;%])").unwrap(), "$1"),
+        (Regex::new(r"\s\.\.\.\s").unwrap(), "..."),
+        // Just remove left padding. Punctuation in numbers won't be padded.
+        (Regex::new(r"\s([:,])").unwrap(), "$1"),
+    ];
+
+    // starting quotes
+    static ref STARTING_QUOTES: Vec<(Regex, &'static str)> = vec![
+        (Regex::new(r"([ (\[{<])\s``").unwrap(), "$1``"),
+        (Regex::new(r"(``)\s").unwrap(), "$1"),
+        (Regex::new(r"``").unwrap(), "\""),
+    ];
+}
+
+/// The Treebank detokenizer uses the reverse regex operations corresponding to
+/// the Treebank tokenizer's regexes.
+///
+/// Note:
+///
+/// - There're additional assumption mades when undoing the padding of ``[;@#$%&]``
+///   punctuation symbols that isn't presupposed in the TreebankTokenizer.
+/// - There're additional regexes added in reversing the parentheses tokenization,
+///   such as the ``r'([\]\)\}\>])\s([:;,.])'``, which removes the additional right
+///   padding added to the closing parentheses precedding ``[:;,.]``.
+/// - It's not possible to return the original whitespaces as they were because
+///   there wasn't explicit records of where `'\n'`, `'\t'` or `'\s'` were removed at
+///   the text.split() operation.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::api::TokenizerI;
+/// # use lib_nltk::tokenize::destructive::NLTKWordTokenizer;
+/// # use lib_nltk::tokenize::treebank::TreebankWordDetokenizer;
+/// let s = "Good muffins cost $3.88\nin New York.  Please buy me\ntwo of them.\nThanks.";
+/// let tokens = NLTKWordTokenizer::new().tokenize(s);
+/// let result = TreebankWordDetokenizer::new().detokenize(&tokens);
+/// # assert_eq!(result, "Good muffins cost $3.88 in New York. Please buy me two of them. Thanks.");
+/// ```
+#[derive(Debug, Default)]
+pub struct TreebankWordDetokenizer {
+    convert_parentheses: bool,
+}
+impl TreebankWordDetokenizer {
+    /// Initialize a [TreebankWordDetokenizer] that leaves the PTB bracket symbols as is
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets if the PTB symbols are converted back to parentheses and brackets
+    /// (``-LRB-`` to ``(``, ``-RSB-`` to ``]``, etc).
+    ///
+    /// Defaults to 'FALSE' - the symbols are left as is
+    pub fn set_convert_parentheses(mut self, yes: bool) -> Self {
+        self.convert_parentheses = yes;
+        self
+    }
+
+    /// Treebank detokenizer, created by undoing the regexes from
+    /// the TreebankWordTokenizer.tokenize.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::treebank::TreebankWordDetokenizer;
+    /// let tokens = ["Good", "muffins", "cost", "$", "3.88", "in", "New", "-LRB-", "York", "-RRB-", ".",
+    ///     "Please", "-LRB-", "buy", "-RRB-", "me", "two", "of", "them.", "-LRB-", "Thanks", "-RRB-", "."];
+    /// let result = TreebankWordDetokenizer::new().set_convert_parentheses(true).detokenize(&tokens);
+    /// # assert_eq!(result, "Good muffins cost $3.88 in New (York). Please (buy) me two of them. (Thanks).");
+    /// ```
+    pub fn detokenize<S: AsRef<str>>(&self, tokens: &[S]) -> String {
+        let text = tokens
+            .iter()
+            .map(AsRef::as_ref)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        // Add extra space to make things easier
+        let mut text = format!(" {} ", text);
+
+        // Reverse the contractions regexes.
+        // Note: CONTRACTIONS4 are not used in tokenization.
+        for regexp in CONTRACTIONS3.iter().chain(CONTRACTIONS2.iter()) {
+            text = regexp
+                .replace_all(&text, |caps: &Captures| {
+                    // Keep anything matched after the contraction, i.e. the whitespace after "wanna"
+                    let tail =
+                        &caps[0][caps.get(2).unwrap().end() - caps.get(0).unwrap().start()..];
+                    format!("{}{}{}", &caps[1], &caps[2], tail)
+                })
+                .into_owned();
+        }
+
+        // Reverse the regexes applied for ending quotes.
+        for (regexp, substitution) in ENDING_QUOTES.iter() {
+            text = regexp.replace_all(&text, *substitution).into_owned();
+        }
+
+        // Undo the space padding.
+        let mut text = text.trim().to_string();
+
+        // Reverse the padding on double dashes.
+        text = DOUBLE_DASHES.replace_all(&text, "--").into_owned();
+
+        if self.convert_parentheses {
+            for (bracket, symbol) in CONVERT_PARENTHESES {
+                text = text.replace(symbol, bracket);
+            }
+        }
+
+        // Reverse the padding regexes applied for parenthesis/brackets.
+        for (regexp, substitution) in PARENS_BRACKETS.iter() {
+            text = regexp.replace_all(&text, *substitution).into_owned();
+        }
+
+        // Reverse the regexes applied for punctuations.
+        for (regexp, substitution) in PUNCTUATION.iter() {
+            text = regexp.replace_all(&text, *substitution).into_owned();
+        }
+
+        // Reverse the regexes applied for starting quotes.
+        for (regexp, substitution) in STARTING_QUOTES.iter() {
+            text = regexp.replace_all(&text, *substitution).into_owned();
+        }
+
+        text.trim().to_string()
+    }
+}
+
+#[test]
+fn test_treebank_detokenize_contractions() {
+    let detokenizer = TreebankWordDetokenizer::new();
+    let tokens = [
+        "I", "ca", "n't", "believe", "I", "'m", "gon", "na", "wan", "na", "leave", ".",
+    ];
+    assert_eq!(
+        detokenizer.detokenize(&tokens),
+        "I can't believe I'm gonna wanna leave."
+    );
+    let tokens = [
+        "I", "can", "not", "say", "``", "hello", "''", "to", "them", "!",
+    ];
+    assert_eq!(
+        detokenizer.detokenize(&tokens),
+        "I cannot say \"hello\" to them!"
+    );
+}
+
+#[test]
+fn test_treebank_detokenize_roundtrip() {
+    use super::api::TokenizerI;
+    use super::destructive::NLTKWordTokenizer;
+
+    let tokenizer = NLTKWordTokenizer::new().set_convert_parentheses(true);
+    let detokenizer = TreebankWordDetokenizer::new().set_convert_parentheses(true);
+    for sent in [
+        "The DUP is similar to the \"religious right\" in the United States.",
+        "She said: \"I'd rather not (really), thanks--it's 50% off?\"",
+        "Costs $3.88; that's it!",
+    ] {
+        assert_eq!(detokenizer.detokenize(&tokenizer.tokenize(sent)), sent);
+    }
+}
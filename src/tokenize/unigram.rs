@@ -0,0 +1,592 @@
+//! Unigram Language Model Tokenizer
+//!
+//! The unigram language model splits a text into the subwords (pieces) of a vocabulary,
+//! picking the segmentation with the highest probability, the pieces being independent
+//! of each other:
+//!
+//! ```rust
+//! # extern crate lib_nltk;
+//! # use lib_nltk::probability::FreqDist;
+//! # use lib_nltk::tokenize::api::TokenizerI;
+//! # use lib_nltk::tokenize::unigram::UnigramTokenizer;
+//! let mut texts: FreqDist<&str> = FreqDist::default();
+//! texts.init(["the lower newest", "the widest low", "the newer lowest", "the lowest"]);
+//! let tokenizer = UnigramTokenizer::train(&texts, 30);
+//! let text = "the newest low";
+//! let result = tokenizer.tokenize(text);
+//! # assert_eq!(result.concat(), text);
+//! let ids: Vec<usize> = tokenizer.encode(text).into_iter().map(|(id, _)| id).collect();
+//! assert_eq!(tokenizer.decode(&ids).unwrap(), text);
+//! ```
+//!
+//! Whitespace is a symbol like any other, a piece may start with it (shown as ``▁``
+//! in the vocabulary), so the text is put back together as is by
+//! [UnigramTokenizer::decode].
+//!
+//! Reference:
+//! Taku Kudo. 2018. Subword Regularization: Improving Neural Network Translation
+//! Models with Multiple Subword Candidates. In Proceedings of ACL 2018.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+
+use rand::Rng;
+
+use crate::probability::{add_log, FreqDist};
+
+use super::api::TokenizerI;
+use super::util::{Token, TokenizeError};
+
+/// The symbol standing for a space in the pieces of the vocabulary
+pub const SPACE: char = '▁';
+
+/// The piece of the characters that aren't in the vocabulary, its id is always 0
+pub const UNKNOWN: &str = "<unk>";
+
+// The longest seed piece, in chars
+const MAX_PIECE_LEN: usize = 16;
+// The number of EM iterations between two prunings of the vocabulary
+const EM_ITERATIONS: usize = 2;
+// The share of the vocabulary kept by a pruning
+const SHRINK_FACTOR: f64 = 0.75;
+// The expected count below which a piece is dropped, and the least count of a character
+const MIN_COUNT: f64 = 0.5;
+// The score of an unknown character, below the lowest score of a piece
+const UNKNOWN_PENALTY: f64 = 10.0;
+
+// The edges of the lattice of a text: for each char, the pieces starting there,
+// as the index of the char following the piece and the id of the piece
+type Lattice = Vec<Vec<(usize, usize)>>;
+
+/// A subword tokenizer segmenting text with a unigram language model.
+///
+/// The vocabulary is the unknown piece, then the pieces with their scores, the base 2
+/// logarithms of their probabilities; the id of a piece is its index in the vocabulary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnigramTokenizer {
+    pieces: Vec<(String, f64)>,
+    ids: HashMap<String, usize>,
+    max_len: usize,
+    unknown_score: f64,
+}
+
+impl UnigramTokenizer {
+    /// Initializes a [UnigramTokenizer] with the *pieces* of its vocabulary and their scores,
+    /// the base 2 logarithms of their probabilities.
+    ///
+    /// The pieces write a space as [SPACE].
+    pub fn new<S: AsRef<str>>(pieces: &[(S, f64)]) -> Self {
+        let mut tokenizer = Self {
+            pieces: vec![(UNKNOWN.to_string(), 0.0)],
+            ids: HashMap::new(),
+            max_len: 0,
+            unknown_score: 0.0,
+        };
+        tokenizer.pieces.extend(
+            pieces
+                .iter()
+                .map(|(piece, score)| (piece.as_ref().to_string(), *score)),
+        );
+        tokenizer.reindex();
+        tokenizer
+    }
+
+    /// Trains a vocabulary of about *vocab_size* pieces on the frequency distribution of
+    /// *texts* (sentences or words).
+    ///
+    /// The seed vocabulary is every character and the frequent substrings of the texts;
+    /// the scores of the pieces are fit by expectation maximization, then the pieces whose
+    /// removal least lowers the likelihood of the texts are pruned, until *vocab_size*
+    /// pieces are left. The characters are never pruned, so the vocabulary is larger
+    /// than *vocab_size* if there are more characters than that.
+    pub fn train(texts: &FreqDist<&str>, vocab_size: usize) -> Self {
+        let mut texts: Vec<(&str, usize)> = texts
+            .list()
+            .into_iter()
+            .filter(|(text, _)| !text.is_empty())
+            .map(|(&text, &count)| (text, count))
+            .collect();
+        texts.sort_unstable();
+
+        let mut tokenizer = Self::new(&seed_pieces(&texts));
+        loop {
+            for _ in 0..EM_ITERATIONS {
+                let counts = tokenizer.expectation(&texts);
+                tokenizer.maximization(&counts);
+            }
+            if tokenizer.pieces.len() <= vocab_size {
+                break;
+            }
+            let counts = tokenizer.expectation(&texts);
+            let size = vocab_size.max((tokenizer.pieces.len() as f64 * SHRINK_FACTOR) as usize);
+            if !tokenizer.prune(&counts, size) {
+                break;
+            }
+        }
+        tokenizer
+    }
+
+    /// Returns the pieces of the vocabulary and their scores, by id
+    pub fn pieces(&self) -> &[(String, f64)] {
+        &self.pieces
+    }
+
+    /// Returns the id of *piece*, if it is in the vocabulary
+    pub fn token_to_id(&self, piece: &str) -> Option<usize> {
+        self.ids.get(piece).copied()
+    }
+
+    /// Returns the piece of *id*, if it is in the vocabulary
+    pub fn id_to_token(&self, id: usize) -> Option<&str> {
+        self.pieces.get(id).map(|(piece, _)| piece.as_str())
+    }
+
+    /// Returns the ids of the pieces of the most likely segmentation of *text*,
+    /// along with their spans.
+    ///
+    /// A character that isn't in the vocabulary is a piece of its own, with the id of [UNKNOWN].
+    pub fn encode(&self, text: &str) -> Vec<(usize, Token)> {
+        let bounds = char_bounds(text);
+        let lattice = self.lattice(text, &bounds, None);
+        let n = bounds.len() - 1;
+        // the best score of the segmentations up to each char, and the last piece of the best one
+        let mut best = vec![(f64::NEG_INFINITY, 0, 0); n + 1];
+        best[0].0 = 0.0;
+        for i in 0..n {
+            for &(j, id) in &lattice[i] {
+                let score = best[i].0 + self.score(id);
+                if score > best[j].0 {
+                    best[j] = (score, i, id);
+                }
+            }
+        }
+        let mut result = Vec::new();
+        let mut j = n;
+        while j > 0 {
+            let (_, i, id) = best[j];
+            result.push((id, (bounds[i], bounds[j])));
+            j = i;
+        }
+        result.reverse();
+        result
+    }
+
+    /// Returns the ids of the pieces of a segmentation of *text* sampled from the
+    /// segmentations, along with their spans, for subword regularization.
+    ///
+    /// A segmentation is sampled with a probability proportional to its probability raised
+    /// to the power of *alpha*: the higher *alpha*, the closer to [UnigramTokenizer::encode],
+    /// and an *alpha* of 0 samples uniformly.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::unigram::UnigramTokenizer;
+    /// let tokenizer = UnigramTokenizer::new(&[("a", -2.0), ("b", -2.0), ("ab", -3.0)]);
+    /// let mut rng = rand::thread_rng();
+    /// let sample = tokenizer.sample_encode("ab", 1.0, &mut rng);
+    /// assert!(sample == vec![(3, (0, 2))] || sample == vec![(1, (0, 1)), (2, (1, 2))]);
+    /// ```
+    pub fn sample_encode<R: Rng + ?Sized>(
+        &self,
+        text: &str,
+        alpha: f64,
+        rng: &mut R,
+    ) -> Vec<(usize, Token)> {
+        let bounds = char_bounds(text);
+        let lattice = self.lattice(text, &bounds, None);
+        let n = bounds.len() - 1;
+        let forward = self.forward(&lattice, alpha);
+
+        let mut incoming = vec![Vec::new(); n + 1];
+        for (i, edges) in lattice.iter().enumerate() {
+            for &(j, id) in edges {
+                incoming[j].push((i, id));
+            }
+        }
+        let mut result = Vec::new();
+        let mut j = n;
+        while j > 0 {
+            let mut r: f64 = rng.gen();
+            let mut chosen = incoming[j][0];
+            for &(i, id) in &incoming[j] {
+                r -= f64::powf(2.0, forward[i] + alpha * self.score(id) - forward[j]);
+                chosen = (i, id);
+                if r <= 0.0 {
+                    break;
+                }
+            }
+            let (i, id) = chosen;
+            result.push((id, (bounds[i], bounds[j])));
+            j = i;
+        }
+        result.reverse();
+        result
+    }
+
+    /// Returns the text of the pieces *ids*, with the [SPACE]s turned back into spaces.
+    ///
+    /// Returns [TokenizeError::UnknownId] for an id that isn't in the vocabulary.
+    pub fn decode(&self, ids: &[usize]) -> Result<String, TokenizeError> {
+        let mut text = String::new();
+        for &id in ids {
+            let piece = self.id_to_token(id).ok_or(TokenizeError::UnknownId(id))?;
+            text.extend(piece.chars().map(|c| if c == SPACE { ' ' } else { c }));
+        }
+        Ok(text)
+    }
+
+    /// Writes the pieces and their scores out in the plain text format read by
+    /// [UnigramTokenizer::load], one piece per line.
+    pub fn save<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        for (piece, score) in &self.pieces[1..] {
+            writeln!(writer, "{}\t{}", escape(piece), score)?;
+        }
+        Ok(())
+    }
+
+    /// Reads in the pieces written out by [UnigramTokenizer::save].
+    ///
+    /// Returns an [io::ErrorKind::InvalidData] error for a malformed line.
+    pub fn load<R: BufRead>(reader: R) -> io::Result<Self> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid unigram vocabulary line: {:?}", line),
+            )
+        };
+
+        let mut pieces = Vec::new();
+        for line in reader.lines() {
+            let line = line?;
+            if line.is_empty() {
+                continue;
+            }
+            let (piece, score) = line.split_once('\t').ok_or_else(|| invalid(&line))?;
+            let score = score.parse().map_err(|_| invalid(&line))?;
+            pieces.push((unescape(piece).ok_or_else(|| invalid(&line))?, score));
+        }
+        Ok(Self::new(&pieces))
+    }
+
+    // Rebuilds the index of the pieces, after the vocabulary changed
+    fn reindex(&mut self) {
+        self.ids = self
+            .pieces
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(id, (piece, _))| (piece.clone(), id))
+            .collect();
+        self.max_len = self
+            .pieces
+            .iter()
+            .skip(1)
+            .map(|(piece, _)| piece.chars().count())
+            .max()
+            .unwrap_or(0);
+        self.unknown_score = self.pieces[1..]
+            .iter()
+            .map(|&(_, score)| score)
+            .fold(0.0, f64::min)
+            - UNKNOWN_PENALTY;
+    }
+
+    fn score(&self, id: usize) -> f64 {
+        match id {
+            0 => self.unknown_score,
+            _ => self.pieces[id].1,
+        }
+    }
+
+    // Finds the pieces of the vocabulary (but *exclude*) in the text, an unknown char
+    // being a piece of its own
+    fn lattice(&self, text: &str, bounds: &[usize], exclude: Option<usize>) -> Lattice {
+        let n = bounds.len() - 1;
+        let mut lattice = vec![Vec::new(); n];
+        for (i, edges) in lattice.iter_mut().enumerate() {
+            let mut piece = String::new();
+            for j in i + 1..=n.min(i + self.max_len) {
+                piece.extend(text[bounds[j - 1]..bounds[j]].chars().map(|c| {
+                    if c == ' ' {
+                        SPACE
+                    } else {
+                        c
+                    }
+                }));
+                match self.token_to_id(&piece) {
+                    Some(id) if Some(id) != exclude => edges.push((j, id)),
+                    _ => {}
+                }
+            }
+            if !edges.iter().any(|&(j, _)| j == i + 1) {
+                edges.push((i + 1, 0));
+            }
+        }
+        lattice
+    }
+
+    // The log of the summed probabilities of the segmentations up to each char,
+    // the probabilities of the pieces raised to the power of alpha
+    fn forward(&self, lattice: &Lattice, alpha: f64) -> Vec<f64> {
+        let mut forward = vec![f64::NEG_INFINITY; lattice.len() + 1];
+        forward[0] = 0.0;
+        for (i, edges) in lattice.iter().enumerate() {
+            for &(j, id) in edges {
+                forward[j] = add_log(forward[j], forward[i] + alpha * self.score(id));
+            }
+        }
+        forward
+    }
+
+    // The expected counts of the pieces in the texts, by id
+    fn expectation(&self, texts: &[(&str, usize)]) -> Vec<f64> {
+        let mut counts = vec![0.0; self.pieces.len()];
+        for &(text, count) in texts {
+            let bounds = char_bounds(text);
+            let lattice = self.lattice(text, &bounds, None);
+            let forward = self.forward(&lattice, 1.0);
+            let mut backward = vec![f64::NEG_INFINITY; lattice.len() + 1];
+            backward[lattice.len()] = 0.0;
+            for (i, edges) in lattice.iter().enumerate().rev() {
+                for &(j, id) in edges {
+                    backward[i] = add_log(backward[i], self.score(id) + backward[j]);
+                }
+            }
+            let total = forward[lattice.len()];
+            for (i, edges) in lattice.iter().enumerate() {
+                for &(j, id) in edges {
+                    let log_prob = forward[i] + self.score(id) + backward[j] - total;
+                    counts[id] += count as f64 * f64::powf(2.0, log_prob);
+                }
+            }
+        }
+        counts
+    }
+
+    // Refits the scores of the pieces to their expected counts, dropping the pieces
+    // expected less than MIN_COUNT times but the single characters
+    fn maximization(&mut self, counts: &[f64]) {
+        let pieces: Vec<(String, f64)> = self
+            .pieces
+            .drain(1..)
+            .zip(&counts[1..])
+            .filter_map(|((piece, _), &count)| {
+                let single = piece.chars().nth(1).is_none();
+                match (count < MIN_COUNT, single) {
+                    (true, false) => None,
+                    _ => Some((piece, count.max(MIN_COUNT))),
+                }
+            })
+            .collect();
+        let total: f64 = pieces.iter().map(|(_, count)| count).sum();
+        self.pieces.extend(
+            pieces
+                .into_iter()
+                .map(|(piece, count)| (piece, f64::log2(count / total))),
+        );
+        self.reindex();
+    }
+
+    // Prunes the pieces whose removal least lowers the likelihood of the texts, the single
+    // characters aside, down to size pieces. Returns if any piece was pruned.
+    fn prune(&mut self, counts: &[f64], size: usize) -> bool {
+        let mut losses: Vec<(f64, usize)> = Vec::new();
+        for (id, ((piece, score), count)) in self.pieces.iter().zip(counts).enumerate().skip(1) {
+            let piece = piece.replace(SPACE, " ");
+            let bounds = char_bounds(&piece);
+            if bounds.len() <= 2 {
+                continue;
+            }
+            // the score of the best segmentation of the piece without it
+            let lattice = self.lattice(&piece, &bounds, Some(id));
+            let mut best = vec![f64::NEG_INFINITY; bounds.len()];
+            best[0] = 0.0;
+            for (i, edges) in lattice.iter().enumerate() {
+                for &(j, other) in edges {
+                    best[j] = best[j].max(best[i] + self.score(other));
+                }
+            }
+            let loss = count * (score - best[bounds.len() - 1]);
+            losses.push((loss, id));
+        }
+        let singles = self.pieces.len() - 1 - losses.len();
+        let keep = size.saturating_sub(singles + 1);
+        if keep >= losses.len() {
+            return false;
+        }
+        losses.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(&b.1)));
+        let mut pruned = vec![false; self.pieces.len()];
+        for &(_, id) in &losses[keep..] {
+            pruned[id] = true;
+        }
+        let mut id = 0;
+        self.pieces.retain(|_| {
+            id += 1;
+            !pruned[id - 1]
+        });
+        self.reindex();
+        true
+    }
+}
+
+impl TokenizerI for UnigramTokenizer {
+    fn tokenize<'a>(&self, sent: &'a str) -> Vec<&'a str> {
+        self.span_tokenize(sent)
+            .into_iter()
+            .map(|(start, end)| &sent[start..end])
+            .collect()
+    }
+
+    fn span_tokenize(&self, sent: &str) -> Vec<Token> {
+        self.encode(sent)
+            .into_iter()
+            .map(|(_, span)| span)
+            .collect()
+    }
+}
+
+// The byte offsets of the chars of the text, and of its end
+fn char_bounds(text: &str) -> Vec<usize> {
+    text.char_indices()
+        .map(|(i, _)| i)
+        .chain([text.len()])
+        .collect()
+}
+
+// The characters, and the most frequent substrings of the texts that don't have
+// whitespace past their first character, scored by their frequency
+fn seed_pieces(texts: &[(&str, usize)]) -> Vec<(String, f64)> {
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for &(text, count) in texts {
+        let chars: Vec<char> = text
+            .chars()
+            .map(|c| if c == ' ' { SPACE } else { c })
+            .collect();
+        for i in 0..chars.len() {
+            for j in i + 1..=chars.len().min(i + MAX_PIECE_LEN) {
+                if j > i + 1 && (chars[j - 1].is_whitespace() || chars[j - 1] == SPACE) {
+                    break;
+                }
+                let piece: String = chars[i..j].iter().collect();
+                *counts.entry(piece).or_insert(0) += count;
+            }
+        }
+    }
+    let mut pieces: Vec<(String, usize)> = counts
+        .into_iter()
+        .filter(|(piece, count)| *count > 1 || piece.chars().nth(1).is_none())
+        .collect();
+    pieces.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    let total: usize = pieces.iter().map(|(_, count)| count).sum();
+    pieces
+        .into_iter()
+        .map(|(piece, count)| (piece, f64::log2(count as f64 / total as f64)))
+        .collect()
+}
+
+// Escapes the backslashes, tabs and line breaks of a piece, to save it on a line
+fn escape(piece: &str) -> String {
+    let mut escaped = String::new();
+    for c in piece.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(piece: &str) -> Option<String> {
+    let mut unescaped = String::new();
+    let mut chars = piece.chars();
+    while let Some(c) = chars.next() {
+        unescaped.push(match c {
+            '\\' => match chars.next()? {
+                '\\' => '\\',
+                't' => '\t',
+                'n' => '\n',
+                'r' => '\r',
+                _ => return None,
+            },
+            _ => c,
+        });
+    }
+    Some(unescaped)
+}
+
+#[test]
+fn test_unigram() {
+    let tokenizer = UnigramTokenizer::new(&[
+        ("▁", -3.0),
+        ("a", -3.0),
+        ("b", -3.0),
+        ("ab", -4.0),
+        ("▁ab", -5.0),
+    ]);
+    let text = "ab ab\tc";
+    assert_eq!(tokenizer.tokenize(text), vec!["ab", " ab", "\t", "c"]);
+    let ids: Vec<usize> = tokenizer
+        .encode(text)
+        .into_iter()
+        .map(|(id, _)| id)
+        .collect();
+    assert_eq!(ids, vec![4, 5, 0, 0]);
+    assert_eq!(tokenizer.decode(&ids[..2]).unwrap(), "ab ab");
+    assert!(matches!(
+        tokenizer.decode(&[6]),
+        Err(TokenizeError::UnknownId(6))
+    ));
+
+    // the counts of the segmentations add up to the counts of the texts
+    let counts = tokenizer.expectation(&[("ab", 3)]);
+    assert!((counts[2] - 0.6).abs() < 1e-9 && (counts[3] - 0.6).abs() < 1e-9);
+    assert!((counts[4] - 2.4).abs() < 1e-9);
+
+    let mut saved = Vec::new();
+    let tokenizer = UnigramTokenizer::new(&[("a\tb\\", -1.0), ("\n", -2.0)]);
+    tokenizer.save(&mut saved).unwrap();
+    assert_eq!(UnigramTokenizer::load(saved.as_slice()).unwrap(), tokenizer);
+    assert!(UnigramTokenizer::load("ab".as_bytes()).is_err());
+}
+
+#[test]
+fn test_unigram_train() {
+    let mut texts: FreqDist<&str> = FreqDist::default();
+    texts.init([
+        "low lower lowest",
+        "new newer newest",
+        "wide wider widest",
+        "low lower lowest",
+    ]);
+    let tokenizer = UnigramTokenizer::train(&texts, 25);
+    assert!(tokenizer.pieces().len() <= 25);
+    let total: f64 = tokenizer.pieces()[1..]
+        .iter()
+        .map(|(_, score)| f64::powf(2.0, *score))
+        .sum();
+    assert!((total - 1.0).abs() < 1e-9);
+
+    let text = "the lowest widest";
+    let spans = tokenizer.span_tokenize(text);
+    assert_eq!(spans.first().map(|s| s.0), Some(0));
+    assert!(spans.windows(2).all(|pair| pair[0].1 == pair[1].0));
+    let ids: Vec<usize> = tokenizer
+        .encode("lowest wider")
+        .iter()
+        .map(|&(id, _)| id)
+        .collect();
+    assert_eq!(tokenizer.decode(&ids).unwrap(), "lowest wider");
+
+    let mut rng = rand::thread_rng();
+    for _ in 0..10 {
+        let sample = tokenizer.sample_encode(text, 0.5, &mut rng);
+        let spans: Vec<Token> = sample.into_iter().map(|(_, span)| span).collect();
+        assert_eq!(spans.last().map(|s| s.1), Some(text.len()));
+        assert!(spans.windows(2).all(|pair| pair[0].1 == pair[1].0));
+    }
+}
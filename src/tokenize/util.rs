@@ -3,17 +3,277 @@
 use regex::Regex;
 use unicode_segmentation::UnicodeSegmentation;
 
+use std::collections::HashMap;
 use std::fmt;
 
-/// Represets the sequence of a `(starting, ending)` tuple
+/// Represets the sequence of a `(starting, ending)` tuple, as byte offsets.
+///
+/// Every tokenizer returns its spans in bytes, so that `&text[start..end]` is the token.
+/// Use an [OffsetMap] to convert them into a [Span] in another [SpanUnit].
 pub type Token = (usize, usize);
 
-/// Return the offsets of the tokens in *sent*, as a sequence of `(start, end)`
+/// The unit the offsets of a [Span] are counted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpanUnit {
+    /// UTF-8 bytes, the offsets used to slice a `str`
+    Byte,
+    /// Unicode scalar values, the items of [str::chars]
+    Char,
+    /// Extended grapheme clusters, as segmented by [UnicodeSegmentation::graphemes]
+    Grapheme,
+    /// UTF-16 code units, the offsets used by JavaScript and the browser DOM
+    Utf16,
+}
+
+/// A `(start, end)` span together with the unit its offsets are counted in.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::util::{OffsetMap, Span, SpanUnit};
+/// let text = "naïve 😀 café";
+/// let map = OffsetMap::new(text);
+/// let span = Span::from((12, 17));
+/// assert_eq!(&text[span.start..span.end], "café");
+/// let result = map.convert_span(span, SpanUnit::Utf16).unwrap();
+/// # assert_eq!(result, Span::new(9, 13, SpanUnit::Utf16));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// The offset of the start of the span
+    pub start: usize,
+    /// The offset just past the end of the span
+    pub end: usize,
+    /// The unit of both offsets
+    pub unit: SpanUnit,
+}
+
+impl Span {
+    /// Initializes a [Span] from its offsets and their unit
+    pub fn new(start: usize, end: usize, unit: SpanUnit) -> Self {
+        Self { start, end, unit }
+    }
+
+    /// Returns the length of the span, in its unit
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns true if the span doesn't cover anything
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+/// A tokenizer's [Token] is a span of byte offsets
+impl From<Token> for Span {
+    fn from((start, end): Token) -> Self {
+        Self::new(start, end, SpanUnit::Byte)
+    }
+}
+
+/// Converts offsets in one [SpanUnit] of a string into another.
+///
+/// The boundaries of every unit are computed once when the map is built,
+/// a conversion is then a lookup or a binary search.
+/// Converting an offset that doesn't fall on a boundary of its unit, such as a byte
+/// inside a character or a UTF-16 offset between the halves of a surrogate pair,
+/// is a [TokenizeError::InvalidOffset] rather than a silently shifted offset.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::util::{OffsetMap, SpanUnit};
+/// // "e\u{301}" is one grapheme, made up of two chars
+/// let map = OffsetMap::new("cafe\u{301} 👍🏽");
+/// assert_eq!(map.len(SpanUnit::Byte), 15);
+/// assert_eq!(map.len(SpanUnit::Char), 8);
+/// assert_eq!(map.len(SpanUnit::Grapheme), 6);
+/// assert_eq!(map.len(SpanUnit::Utf16), 10);
+/// let result = map.convert(7, SpanUnit::Byte, SpanUnit::Grapheme).unwrap();
+/// # assert_eq!(result, 5);
+/// assert!(map.convert(4, SpanUnit::Char, SpanUnit::Grapheme).is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct OffsetMap {
+    // The byte offset of each char, followed by the length of the string
+    chars: Vec<usize>,
+    // The UTF-16 offset of each char, followed by the UTF-16 length of the string
+    utf16: Vec<usize>,
+    // The byte offset of each grapheme, followed by the length of the string
+    graphemes: Vec<usize>,
+}
+
+impl OffsetMap {
+    /// Builds the map of the offsets of *text*
+    pub fn new(text: &str) -> Self {
+        let mut chars = Vec::with_capacity(text.len() + 1);
+        let mut utf16 = Vec::with_capacity(text.len() + 1);
+        let mut utf16_offset = 0;
+        for (i, c) in text.char_indices() {
+            chars.push(i);
+            utf16.push(utf16_offset);
+            utf16_offset += c.len_utf16();
+        }
+        chars.push(text.len());
+        utf16.push(utf16_offset);
+
+        let mut graphemes: Vec<usize> = text.grapheme_indices(true).map(|(i, _)| i).collect();
+        graphemes.push(text.len());
+
+        Self {
+            chars,
+            utf16,
+            graphemes,
+        }
+    }
+
+    /// Returns the length of the string, in the given unit
+    pub fn len(&self, unit: SpanUnit) -> usize {
+        match unit {
+            SpanUnit::Byte => self.chars[self.chars.len() - 1],
+            SpanUnit::Char => self.chars.len() - 1,
+            SpanUnit::Grapheme => self.graphemes.len() - 1,
+            SpanUnit::Utf16 => self.utf16[self.utf16.len() - 1],
+        }
+    }
+
+    /// Returns true if the string is empty
+    pub fn is_empty(&self) -> bool {
+        self.len(SpanUnit::Byte) == 0
+    }
+
+    /// Converts the *offset* counted in the unit *from* into the unit *to*.
+    ///
+    /// Returns [TokenizeError::InvalidOffset] if the offset isn't a boundary of
+    /// *from*, or isn't a boundary of *to* (such as a char inside a grapheme).
+    pub fn convert(
+        &self,
+        offset: usize,
+        from: SpanUnit,
+        to: SpanUnit,
+    ) -> Result<usize, TokenizeError> {
+        let byte = self.byte_offset(offset, from)?;
+        self.unit_offset(byte, to)
+            .ok_or(TokenizeError::InvalidOffset(offset))
+    }
+
+    /// Converts the *span* into the unit *to*.
+    ///
+    /// Returns [TokenizeError::InvalidOffset] for the first of its offsets that can't be converted.
+    pub fn convert_span(&self, span: Span, to: SpanUnit) -> Result<Span, TokenizeError> {
+        Ok(Span::new(
+            self.convert(span.start, span.unit, to)?,
+            self.convert(span.end, span.unit, to)?,
+            to,
+        ))
+    }
+
+    /// Converts the byte offsets of the *tokens* returned by a tokenizer into spans in the unit *to*.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # extern crate lib_nltk;
+    /// # use lib_nltk::tokenize::api::TokenizerI;
+    /// # use lib_nltk::tokenize::destructive::NLTKWordTokenizer;
+    /// # use lib_nltk::tokenize::util::{OffsetMap, Span, SpanUnit};
+    /// let text = "Zoë's 🍕 costs €5.";
+    /// let map = OffsetMap::new(text);
+    /// let spans = map.convert_tokens(&NLTKWordTokenizer::new().span_tokenize(text), SpanUnit::Utf16).unwrap();
+    /// # assert_eq!(spans, vec![Span::new(0, 3, SpanUnit::Utf16), Span::new(3, 5, SpanUnit::Utf16), Span::new(6, 8, SpanUnit::Utf16), Span::new(9, 14, SpanUnit::Utf16), Span::new(15, 17, SpanUnit::Utf16), Span::new(17, 18, SpanUnit::Utf16)]);
+    /// ```
+    pub fn convert_tokens(
+        &self,
+        tokens: &[Token],
+        to: SpanUnit,
+    ) -> Result<Vec<Span>, TokenizeError> {
+        tokens
+            .iter()
+            .map(|&token| self.convert_span(token.into(), to))
+            .collect()
+    }
+
+    // Returns the byte offset of the given offset, if it is a boundary of its unit
+    fn byte_offset(&self, offset: usize, unit: SpanUnit) -> Result<usize, TokenizeError> {
+        let byte = match unit {
+            SpanUnit::Byte => self.chars.binary_search(&offset).ok().map(|_| offset),
+            SpanUnit::Char => self.chars.get(offset).copied(),
+            SpanUnit::Grapheme => self.graphemes.get(offset).copied(),
+            SpanUnit::Utf16 => self
+                .utf16
+                .binary_search(&offset)
+                .ok()
+                .map(|i| self.chars[i]),
+        };
+        byte.ok_or(TokenizeError::InvalidOffset(offset))
+    }
+
+    // Returns the offset in the given unit of a byte offset on a char boundary,
+    // if it is a boundary of that unit
+    fn unit_offset(&self, byte: usize, unit: SpanUnit) -> Option<usize> {
+        match unit {
+            SpanUnit::Byte => Some(byte),
+            SpanUnit::Char => self.chars.binary_search(&byte).ok(),
+            SpanUnit::Grapheme => self.graphemes.binary_search(&byte).ok(),
+            SpanUnit::Utf16 => self.chars.binary_search(&byte).ok().map(|i| self.utf16[i]),
+        }
+    }
+}
+
+#[test]
+fn test_offset_map() {
+    let text = "a\u{1F600}be\u{301}";
+    let map = OffsetMap::new(text);
+    assert_eq!(map.len(SpanUnit::Byte), 9);
+    assert_eq!(map.len(SpanUnit::Char), 5);
+    assert_eq!(map.len(SpanUnit::Grapheme), 4);
+    assert_eq!(map.len(SpanUnit::Utf16), 6);
+
+    let span = Span::new(1, 5, SpanUnit::Byte);
+    assert_eq!(
+        map.convert_span(span, SpanUnit::Char).unwrap(),
+        Span::new(1, 2, SpanUnit::Char)
+    );
+    assert_eq!(
+        map.convert_span(span, SpanUnit::Utf16).unwrap(),
+        Span::new(1, 3, SpanUnit::Utf16)
+    );
+    let span = Span::new(3, 6, SpanUnit::Utf16);
+    assert_eq!(
+        map.convert_span(span, SpanUnit::Byte).unwrap(),
+        Span::new(5, 9, SpanUnit::Byte)
+    );
+    assert_eq!(
+        map.convert_span(span, SpanUnit::Grapheme).unwrap(),
+        Span::new(2, 4, SpanUnit::Grapheme)
+    );
+
+    // between the halves of the surrogate pair, inside a char, inside a grapheme and past the end
+    assert!(matches!(
+        map.convert(2, SpanUnit::Utf16, SpanUnit::Byte),
+        Err(TokenizeError::InvalidOffset(2))
+    ));
+    assert!(matches!(
+        map.convert(2, SpanUnit::Byte, SpanUnit::Char),
+        Err(TokenizeError::InvalidOffset(2))
+    ));
+    assert!(matches!(
+        map.convert(4, SpanUnit::Char, SpanUnit::Grapheme),
+        Err(TokenizeError::InvalidOffset(4))
+    ));
+    assert!(map.convert(6, SpanUnit::Char, SpanUnit::Byte).is_err());
+    assert!(OffsetMap::new("").is_empty());
+}
+
+/// Return the byte offsets of the tokens in *sent*, as a sequence of `(start, end)`
 /// tuples, by splitting the string at each occurrence of *sep*.
 ///
 /// Returns Either an Vec<[Token]> or an
 ///
-/// [TokenizeError::ParseError] if unable to parse the string with the given seperator
+/// [TokenizeError::ParseError] if the seperator is empty
 ///
 /// [TokenizeError::MismatchError] if [Token] boundary would exceed beyond the input string's length
 ///
@@ -28,31 +288,30 @@ pub type Token = (usize, usize);
 /// # assert_eq!(result,expected_result);
 /// ```
 pub fn string_span_tokenize(sent: &str, sep: &str) -> Result<Vec<Token>, TokenizeError> {
-    let temp = sent.split(sep).collect::<Vec<_>>();
-    if temp.is_empty() {
+    if sep.is_empty() {
         return Err(TokenizeError::ParseError);
     }
-    println!("{:?}", temp);
 
     let mut result = Vec::new();
     let mut pos: usize = 0;
 
-    for m in temp {
-        println!("{}", m);
-        let end = pos + m.graphemes(true).count();
-        if end > sent.graphemes(true).count() {
+    for m in sent.split(sep) {
+        let end = pos + m.len();
+        if end > sent.len() {
             return Err(TokenizeError::MismatchError);
         }
         result.push((pos, end));
-        pos += m.graphemes(true).count() + 1;
+        pos = end + sep.len();
     }
 
     Ok(result)
 }
 
-/// Return the offsets of the tokens in *sent*, as a sequence of ``(start, end)``
+/// Return the byte offsets of the tokens in *sent*, as a sequence of ``(start, end)``
 /// tuples, by splitting the string at each successive match of *regexp*.
 ///
+/// Returns [TokenizeError::InvalidPattern] if *regexp* doesn't compile.
+///
 /// # Example
 ///
 /// ```rust
@@ -60,11 +319,11 @@ pub fn string_span_tokenize(sent: &str, sep: &str) -> Result<Vec<Token>, Tokeniz
 /// # use lib_nltk::tokenize::util::regexp_span_tokenize;
 /// let sentence = "The plane, bound for St Petersburg, crashed in Egypt's Sinai desert just 23 minutes after take-off from Sharm el-Sheikh on Saturday.";
 /// let expected = vec![(0, 3),(4, 10),(11, 16),(17, 20),(21, 23),(24, 35),(36, 43),(44, 46),(47, 54),(55, 60),(61, 67),(68, 72),(73, 75),(76, 83),(84, 89),(90, 98),(99, 103),(104, 109),(110, 119),(120, 122),(123, 132)];
-/// let result = regexp_span_tokenize(sentence, r"\s+");
+/// let result = regexp_span_tokenize(sentence, r"\s+").unwrap();
 /// # assert_eq!(result,expected);
 /// ```
-pub fn regexp_span_tokenize(sent: &str, regexp: &str) -> Vec<Token> {
-    let re = Regex::new(regexp).unwrap();
+pub fn regexp_span_tokenize(sent: &str, regexp: &str) -> Result<Vec<Token>, TokenizeError> {
+    let re = Regex::new(regexp).map_err(TokenizeError::InvalidPattern)?;
     let mut result: Vec<Token> = Vec::new();
     let mut index: usize = 0;
     let matches = re.find_iter(sent);
@@ -74,7 +333,7 @@ pub fn regexp_span_tokenize(sent: &str, regexp: &str) -> Vec<Token> {
     }
     result.push((index, sent.len()));
 
-    result
+    Ok(result)
 }
 #[test]
 fn test_regexp_span_tokenize() {
@@ -95,8 +354,12 @@ fn test_regexp_span_tokenize() {
         (66, 73),
     ];
     let sentence = "Good muffins cost $3.88\nin New York.  Please buy me two of them.\n\nThanks.";
-    let result = regexp_span_tokenize(sentence, r"\s+");
+    let result = regexp_span_tokenize(sentence, r"\s+").unwrap();
     assert_eq!(result, expected);
+    assert!(matches!(
+        regexp_span_tokenize(sentence, r"(\s+"),
+        Err(TokenizeError::InvalidPattern(_))
+    ));
 }
 
 /// Return a sequence of relative spans, given a sequence of spans.
@@ -109,7 +372,7 @@ fn test_regexp_span_tokenize() {
 /// # use lib_nltk::tokenize::util::spans_to_relative;
 /// let sentence = "Good muffins cost $3.88\nin New York.  Please buy me two of them.\n\nThanks.";
 /// let expected = vec![(0, 4), (1, 7), (1, 4), (1, 5), (1, 2), (1, 3), (1, 5), (2, 6),(1, 3), (1, 2), (1, 3), (1, 2), (1, 5), (2, 7)];
-/// let result = spans_to_relative(regexp_span_tokenize(sentence, r"\s").as_ref());
+/// let result = spans_to_relative(&regexp_span_tokenize(sentence, r"\s").unwrap());
 /// # assert_eq!(result,expected);
 /// ```
 pub fn spans_to_relative(token_span: &[Token]) -> Vec<Token> {
@@ -123,7 +386,6 @@ pub fn spans_to_relative(token_span: &[Token]) -> Vec<Token> {
         }
         result.push((left - prev, right - left));
         prev = *right;
-        println!("left {}, right {}, prev {}", left, right, prev);
     }
 
     result
@@ -185,7 +447,6 @@ fn test_is_cjk() {
 /// let result = xml_escape(s);
 /// # assert_eq!(result,expected_result);
 /// ```
-
 pub fn xml_escape(text: &str) -> String {
     //Probably have a better way, but this should work for now
     text.replace('&', r"&amp;")
@@ -223,53 +484,201 @@ pub fn xml_escape(text: &str) -> String {
 /// ```
 pub fn xml_unescape(text: &str) -> String {
     //Probably have a better way, but this should work for now
-    text.replace(r"&amp;", "&")
-        .replace(r"&gt;", ">")
+    //the ampersand goes last, so that "&amp;lt;" is unescaped to "&lt;"
+    text.replace(r"&gt;", ">")
         .replace(r"&apos;", "'")
         .replace(r"&quot;", "\"")
         .replace(r"&#124;", "|")
         .replace(r"&#91;", "[")
         .replace(r"&#93;", "]")
         .replace(r"&lt;", "<")
+        .replace(r"&amp;", "&")
+}
+
+// The quotes converted by the Treebank tokenizers, and the symbol they are converted to.
+pub(crate) const CONVERTED_QUOTES: [(&str, &str); 3] = [("\"", "``"), ("''", "``"), ("\"", "''")];
+
+// The parentheses and brackets optionally converted by the Treebank tokenizers, and their PTB symbols.
+pub(crate) const CONVERT_PARENTHESES: [(&str, &str); 6] = [
+    ("(", "-LRB-"),
+    (")", "-RRB-"),
+    ("[", "-LSB-"),
+    ("]", "-RSB-"),
+    ("{", "-LCB-"),
+    ("}", "-RCB-"),
+];
+
+/// The forms a tokenizer may have changed the text of a token into, so that
+/// [align_tokens] still finds the token in the original text.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::tokenize::util::{align_tokens, Normalization};
+/// let normalization = Normalization::treebank().add("&amp;", "&").set_case_insensitive(true);
+/// let tokens = ["``", "fish", "&amp;", "chips", "''", "-LRB-", "uk", "-RRB-"];
+/// let result = align_tokens(&tokens, "\"Fish & Chips\" (UK)", &normalization).unwrap();
+/// # assert_eq!(result, vec![(0, 1), (1, 5), (6, 7), (8, 13), (13, 14), (15, 16), (16, 18), (18, 19)]);
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Normalization {
+    forms: HashMap<String, Vec<String>>,
+    case_insensitive: bool,
 }
 
-/// This module attempt to find the offsets of the tokens in *sent*, as a sequence
-/// of ``(start, end)`` tuples, given the tokens and the source string.
+impl Normalization {
+    /// Initializes a [Normalization] without any form, matching the tokens exactly
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Initializes a [Normalization] with the quotes and brackets converted by the
+    /// Treebank tokenizers (``"`` to ``` `` ``` or ``''``, ``(`` to ``-LRB-``, etc).
+    pub fn treebank() -> Self {
+        CONVERTED_QUOTES
+            .iter()
+            .chain(CONVERT_PARENTHESES.iter())
+            .fold(Self::new(), |normalization, (original, symbol)| {
+                normalization.add(symbol, original)
+            })
+    }
+
+    /// Adds *original* as a text a token *token* may have been changed from
+    pub fn add(mut self, token: &str, original: &str) -> Self {
+        self.forms
+            .entry(token.to_string())
+            .or_default()
+            .push(original.to_string());
+        self
+    }
+
+    /// Sets if a token is also matched ignoring case, wherever it is found first
+    /// (e.g. for lower cased tokens).
+    ///
+    /// Defaults to 'FALSE' - the tokens match the text exactly
+    pub fn set_case_insensitive(mut self, yes: bool) -> Self {
+        self.case_insensitive = yes;
+        self
+    }
+
+    // The texts the token may have been changed from, the token itself first
+    fn forms<'a>(&'a self, token: &'a str) -> impl Iterator<Item = &'a str> {
+        std::iter::once(token).chain(
+            self.forms
+                .get(token)
+                .into_iter()
+                .flatten()
+                .map(String::as_str),
+        )
+    }
+}
+
+/// Returns the byte offsets of the *tokens* in *sent*, as a sequence of ``(start, end)``
+/// tuples, given the tokens and the source string.
+///
+/// Each token is searched for from the end of the one before, whatever is between
+/// them (spaces, tabs, line breaks or characters dropped by the tokenizer).
+/// With a *normalization*, a token is also found as any of the texts it may have
+/// been changed from, the one found first winning.
 ///
-/// Returns Either an Vec<[Token]> or a [TokenizeError::MismatchError]
+/// Returns [TokenizeError::MismatchError] if a token isn't found.
 ///
 /// # Example
 ///
 /// ```rust
 /// # extern crate  lib_nltk;
 /// # use lib_nltk::tokenize::util::align_tokens;
-/// let sent = "Hello World";
-/// let token_list = vec!("Hello","World");
-/// let result = align_tokens(token_list.clone(),sent).unwrap();
-/// let expected = vec![(0,5),(6,11)];
+/// let sent = "Hello  World,\n\tagain";
+/// let token_list = vec!("Hello", "World", ",", "again");
+/// let result = align_tokens(&token_list, sent, None).unwrap();
+/// let expected = vec![(0, 5), (7, 12), (12, 13), (15, 20)];
 /// # //ensure get tok the same number of tokens
 /// assert_eq!(token_list.len(),result.len());
 /// assert_eq!(result,expected);
 /// ```
-pub fn align_tokens(tokens: Vec<&str>, _sent: &str) -> Result<Vec<Token>, TokenizeError> {
-    let mut token_span = Vec::new();
-    let mut sentence = String::from(_sent);
-    let mut index: usize = 0;
-    //make sure the tokens list assembled together matches the original string
-    //if this doesn't work, trying to determine the token spans isn't going to work either
-    if tokens.join(" ") != _sent {
-        return Err(TokenizeError::MismatchError);
-    }
+pub fn align_tokens<'n, S, N>(
+    tokens: &[S],
+    sent: &str,
+    normalization: N,
+) -> Result<Vec<Token>, TokenizeError>
+where
+    S: AsRef<str>,
+    N: Into<Option<&'n Normalization>>,
+{
+    let default = Normalization::new();
+    let normalization = normalization.into().unwrap_or(&default);
 
-    for word in tokens {
-        let wsize = word.graphemes(true).count();
-        token_span.push((index, index + wsize));
-        sentence = sentence.split_off(word.len());
-        index += wsize + 1;
+    let mut token_span = Vec::new();
+    let mut index = 0;
+    for token in tokens {
+        let (start, end) = find_token(&sent[index..], token.as_ref(), normalization)
+            .ok_or(TokenizeError::MismatchError)?;
+        token_span.push((index + start, index + end));
+        index += end;
     }
     Ok(token_span)
 }
 
+/// Returns the byte offsets of the *tokens* in *sent* as [align_tokens] does, but with
+/// `None` for a token that isn't found rather than an error, the next token being
+/// searched for from the end of the last one found.
+pub(crate) fn align_found_tokens<S: AsRef<str>>(
+    tokens: &[S],
+    sent: &str,
+    normalization: &Normalization,
+) -> Vec<Option<Token>> {
+    let mut index = 0;
+    tokens
+        .iter()
+        .map(|token| {
+            let (start, end) = find_token(&sent[index..], token.as_ref(), normalization)?;
+            let span = (index + start, index + end);
+            index = span.1;
+            Some(span)
+        })
+        .collect()
+}
+
+// Returns the span of the earliest, then longest, form of token found in rest,
+// matched as is or ignoring case
+fn find_token(rest: &str, token: &str, normalization: &Normalization) -> Option<Token> {
+    let forms: Vec<&str> = normalization
+        .forms(token)
+        .filter(|form| !form.is_empty() || token.is_empty())
+        .collect();
+    forms
+        .iter()
+        .filter_map(|form| rest.find(form).map(|start| (start, start + form.len())))
+        .chain(
+            forms
+                .iter()
+                .filter(|form| normalization.case_insensitive && !form.is_empty())
+                .filter_map(|form| find_caseless(rest, form)),
+        )
+        .min_by_key(|&(start, end)| (start, usize::MAX - end))
+}
+
+// Returns the span of the first occurrence of needle in text, ignoring case
+fn find_caseless(text: &str, needle: &str) -> Option<Token> {
+    let needle: Vec<char> = needle.chars().flat_map(char::to_lowercase).collect();
+    text.char_indices().find_map(|(start, _)| {
+        let mut matched = 0;
+        for (i, c) in text[start..].char_indices() {
+            for c in c.to_lowercase() {
+                if needle.get(matched) != Some(&c) {
+                    return None;
+                }
+                matched += 1;
+            }
+            if matched == needle.len() {
+                return Some((start, start + i + c.len_utf8()));
+            }
+        }
+        None
+    })
+}
+
 // An additional test on the align_tokens method, giving it a much longer input string.
 #[test]
 fn test_align_tokens() {
@@ -297,8 +706,34 @@ fn test_align_tokens() {
         (120, 122),
         (123, 132),
     ];
-    let result = align_tokens(sentence.split_whitespace().collect(), sentence).unwrap();
+    let tokens: Vec<&str> = sentence.split_whitespace().collect();
+    let result = align_tokens(&tokens, sentence, None).unwrap();
     assert_eq!(result, expected);
+
+    let sentence = "Ünïcode  TEXT\r\n(tabs)\tand ÉCOLE";
+    let tokens = ["ünïcode", "text", "-LRB-", "tabs", "-RRB-", "and", "école"];
+    assert!(matches!(
+        align_tokens(&tokens, sentence, None),
+        Err(TokenizeError::MismatchError)
+    ));
+    let normalization = Normalization::treebank().set_case_insensitive(true);
+    let result = align_tokens(&tokens, sentence, &normalization).unwrap();
+    let aligned: Vec<&str> = result.iter().map(|&(s, e)| &sentence[s..e]).collect();
+    assert_eq!(
+        aligned,
+        vec!["Ünïcode", "TEXT", "(", "tabs", ")", "and", "ÉCOLE"]
+    );
+    assert!(matches!(
+        align_tokens(&["and", "tabs"], sentence, &normalization),
+        Err(TokenizeError::MismatchError)
+    ));
+    let normalization = Normalization::new().set_case_insensitive(true);
+    let result = align_tokens(&["the", "cat", "the"], "The cat the", &normalization).unwrap();
+    assert_eq!(result, vec![(0, 3), (4, 7), (8, 11)]);
+    assert_eq!(
+        align_found_tokens(&["the", "dog", "cat"], "the cat", &Normalization::new()),
+        vec![Some((0, 3)), None, Some((4, 7))]
+    );
 }
 
 //****************************************************************************************************************************
@@ -313,6 +748,22 @@ pub enum TokenizeError {
     /// Indicates an issue where the input Vector and input String mismatch resulting in a case,
     /// there's no way the output can ever be valid
     MismatchError,
+    /// Indicates there's no tokenizer available for the requested language
+    UnsupportedLanguage(String),
+    /// Indicates a close parenthesis without a matching open parenthesis, at the given offset
+    UnmatchedCloseParen(usize),
+    /// Indicates an open parenthesis without a matching close parenthesis, at the given offset
+    UnmatchedOpenParen(usize),
+    /// Indicates an offset that isn't on a boundary of its unit, or is past the end of the string
+    InvalidOffset(usize),
+    /// Indicates a regular expression that doesn't compile
+    InvalidPattern(regex::Error),
+    /// Indicates there's no tokenizer registered under the given name
+    UnknownTokenizer(String),
+    /// Indicates a missing, invalid or unknown option of a tokenizer
+    InvalidConfig(String),
+    /// Indicates a token id that isn't in the vocabulary of a subword tokenizer
+    UnknownId(usize),
 }
 
 impl std::error::Error for TokenizeError {}
@@ -321,6 +772,30 @@ impl fmt::Display for TokenizeError {
         match self {
             TokenizeError::ParseError => write!(f, "Parsing Error"),
             TokenizeError::MismatchError => write!(f, "Mismatch Length Error"),
+            TokenizeError::UnsupportedLanguage(language) => {
+                write!(f, "Unsupported Language: {}", language)
+            }
+            TokenizeError::UnmatchedCloseParen(offset) => {
+                write!(f, "Un-matched close paren at byte {}", offset)
+            }
+            TokenizeError::UnmatchedOpenParen(offset) => {
+                write!(f, "Un-matched open paren at byte {}", offset)
+            }
+            TokenizeError::InvalidOffset(offset) => {
+                write!(f, "Invalid offset {}", offset)
+            }
+            TokenizeError::InvalidPattern(error) => {
+                write!(f, "Invalid pattern: {}", error)
+            }
+            TokenizeError::UnknownTokenizer(name) => {
+                write!(f, "Unknown Tokenizer: {}", name)
+            }
+            TokenizeError::InvalidConfig(message) => {
+                write!(f, "Invalid Configuration: {}", message)
+            }
+            TokenizeError::UnknownId(id) => {
+                write!(f, "Unknown Id: {}", id)
+            }
         }
     }
 }
@@ -5,6 +5,34 @@
 //! URL: <https://www.nltk.org/>
 //! For license information, see LICENSE.TXT
 
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Applies *func* to each of the *items* in parallel, on the global rayon thread pool.
+///
+/// The results are in the same order as the items, whatever the order they were
+/// computed in. Requires the ``parallel`` feature.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate lib_nltk;
+/// # use lib_nltk::util::parallelize_preprocess;
+/// let texts = vec!["Good muffins cost $3.88", "in New York."];
+/// let lowered = parallelize_preprocess(&texts, |text| text.to_lowercase());
+/// # assert_eq!(lowered, vec!["good muffins cost $3.88", "in new york."]);
+/// ```
+#[cfg(feature = "parallel")]
+pub fn parallelize_preprocess<I, F, U>(items: I, func: F) -> Vec<U>
+where
+    I: IntoParallelIterator,
+    I::Iter: IndexedParallelIterator,
+    F: Fn(I::Item) -> U + Sync + Send,
+    U: Send,
+{
+    items.into_par_iter().map(func).collect()
+}
+
 //****************************************************************************************************************************
 //  Unused/UnImplemented Fuctions
 //****************************************************************************************************************************
@@ -45,14 +73,16 @@ pub fn clean_html() -> ! {
 pub fn clean_url() -> ! {
     unimplemented!()
 }
-/// # UnImplemented!
-///
-/// Threading tasks are to be done outside this crate
-#[allow(dead_code)]
-pub fn parallelize_preprocess() -> ! {
-    unimplemented!()
-}
 
 //****************************************************************************************************************************
 //
 //****************************************************************************************************************************
+
+#[cfg(feature = "parallel")]
+#[test]
+fn test_parallelize_preprocess() {
+    let items: Vec<usize> = (0..1000).collect();
+    let squares = parallelize_preprocess(&items, |n| n * n);
+    assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+    assert!(parallelize_preprocess(Vec::<String>::new(), |s| s.len()).is_empty());
+}